@@ -1,20 +1,25 @@
+use std::convert::Infallible;
 use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
 
 use crate::types::Points;
+use crate::util::string_id;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Assignment {
-    id: String,
+    id: AssignmentId,
     name: AssignmentName,
     points: Points,
 }
 
 impl Assignment {
-    pub fn new(id: String, name: AssignmentName, points: Points) -> Self {
+    pub fn new(id: AssignmentId, name: AssignmentName, points: Points) -> Self {
         Self { id, name, points }
     }
 
-    pub fn id(&self) -> &str {
+    pub fn id(&self) -> &AssignmentId {
         &self.id
     }
 
@@ -27,7 +32,14 @@ impl Assignment {
     }
 }
 
-#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
+string_id! {
+    /// Parses from a bare id or any Gradescope URL within the assignment, such as a pasted
+    /// submission link
+    AssignmentId, "assignments"
+}
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(transparent)]
 pub struct AssignmentName {
     name: String,
 }
@@ -47,3 +59,11 @@ impl fmt::Display for AssignmentName {
         self.name.fmt(f)
     }
 }
+
+impl FromStr for AssignmentName {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self::new(s.to_owned()))
+    }
+}
@@ -0,0 +1,73 @@
+//! A synchronous facade over [`client::Client`] for quick scripts that don't want to deal with
+//! async, covering the common read operations.
+//!
+//! Each client drives its own single-threaded runtime, so these methods must not be called from
+//! within an async context.
+
+use anyhow::Result;
+use tokio::runtime::{Builder, Runtime};
+
+use crate::assignment::Assignment;
+use crate::client::{self, Auth};
+use crate::course::Course;
+use crate::creds::Creds;
+use crate::regrade::Regrade;
+use crate::selector::ParseWarning;
+use crate::snapshot::CourseSnapshot;
+use crate::stats::RequestStats;
+
+pub struct Client {
+    runtime: Runtime,
+    client: client::Client<Auth>,
+}
+
+impl Client {
+    pub fn from_env() -> Result<Self> {
+        let creds = Creds::from_env()?;
+        Self::login(creds)
+    }
+
+    pub fn login(creds: Creds) -> Result<Self> {
+        let runtime = Builder::new_current_thread().enable_all().build()?;
+        let client = runtime.block_on(async { client::Client::new(creds).await?.login().await })?;
+        Ok(Self { runtime, client })
+    }
+
+    pub fn get_courses(&self) -> Result<(Vec<Course>, Vec<Course>)> {
+        self.runtime.block_on(self.client.get_courses())
+    }
+
+    pub fn get_assignments(&self, course: &Course) -> Result<Vec<Assignment>> {
+        self.runtime.block_on(self.client.get_assignments(course))
+    }
+
+    pub fn get_regrades(&self, course: &Course, assignment: &Assignment) -> Result<Vec<Regrade>> {
+        self.runtime
+            .block_on(self.client.get_regrades(course, assignment))
+    }
+
+    pub fn get_regrades_for<'a>(
+        &self,
+        course: &Course,
+        assignments: impl IntoIterator<Item = &'a Assignment>,
+    ) -> Result<Vec<Vec<Regrade>>> {
+        self.runtime
+            .block_on(self.client.get_regrades_for(course, assignments))
+    }
+
+    pub fn get_all_regrades(&self, course: &Course) -> Result<Vec<(Assignment, Regrade)>> {
+        self.runtime.block_on(self.client.get_all_regrades(course))
+    }
+
+    pub fn get_snapshot(&self, course: &Course) -> Result<CourseSnapshot> {
+        self.runtime.block_on(self.client.get_snapshot(course))
+    }
+
+    pub fn request_stats(&self) -> RequestStats {
+        self.client.request_stats()
+    }
+
+    pub fn take_warnings(&self) -> Vec<ParseWarning> {
+        self.client.take_warnings()
+    }
+}
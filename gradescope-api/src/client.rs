@@ -1,25 +1,34 @@
 use std::collections::HashMap;
+use std::num::NonZeroUsize;
+use std::sync::Mutex;
 use std::time::Duration;
 
-use anyhow::{bail, Context, Result};
-use itertools::Itertools;
+use anyhow::{anyhow, Context, Result};
+use futures::{stream, StreamExt, TryStreamExt};
 use lazy_static::lazy_static;
 use reqwest::redirect::Policy;
-use reqwest::{Client as HttpClient, Response};
+use reqwest::{Client as HttpClient, Response, StatusCode};
 use scraper::{ElementRef, Html};
-use tokio::time::sleep;
+use tokio::sync::Mutex as AsyncMutex;
+use tokio::time::{sleep, sleep_until, Instant};
 use url::Url;
 
-use crate::assignment::{Assignment, AssignmentName};
-use crate::course::{Course, Role};
+use crate::assignment::{Assignment, AssignmentId, AssignmentName};
+use crate::course::{Course, CourseId, CourseWarning, Role};
 use crate::creds::Creds;
+use crate::debug::DebugHook;
+use crate::login::{LoginError, LoginRetry};
 use crate::regrade::Regrade;
-use crate::types::{GraderName, Points, QuestionNumber, QuestionTitle, StudentName};
+use crate::selector::{Lookup, NamedSelector, Page, ParseWarning, Strictness};
+use crate::snapshot::CourseSnapshot;
+use crate::stats::{RequestKind, RequestStats};
+use crate::table::TableColumns;
+use crate::types::{GraderName, Points, QuestionNumber, QuestionTitle, Section, StudentName};
 use crate::util::*;
 
 macro_rules! selectors {
     ($name:ident = $x:expr) => {
-        lazy_static! { static ref $name: scraper::Selector = scraper::Selector::parse($x).unwrap(); }
+        lazy_static! { static ref $name: NamedSelector = NamedSelector::parse(stringify!($name), $x); }
     };
 
     ($name:ident = $x:expr, $($names:ident = $xs:expr),+) => {
@@ -32,41 +41,129 @@ macro_rules! selectors {
 
 selectors! {
     AUTHENTICITY_TOKEN = "form[action='/login'] input[name=authenticity_token]",
+    LOGIN_ALERT = ".alert-flashMessage, .alert-error",
+    COURSE_LIST = ".courseList",
     COURSE_LIST_HEADING = ".pageHeading",
     COURSE = ".courseBox",
     COURSE_SHORT_NAME = ".courseBox--shortname",
     COURSE_NAME = ".courseBox--name",
+    ASSIGNMENT_TABLE = "#assignments-instructor-table, #assignments-student-table",
     ASSIGNMENT_ROW = "tr.js-assignmentTableAssignmentRow",
     TD = "td",
     A = "a",
-    REGRADE_ROW = "table.js-regradeRequestsTable > tbody > tr"
+    REGRADE_TABLE = "table.js-regradeRequestsTable",
+    REGRADE_HEADER = "thead > tr > th",
+    REGRADE_ROW = "tbody > tr",
+    REGRADE_LINK = "a[href*='regrade_request']"
 }
 
+/// The columns every regrade table has, found by header
+const REGRADE_COLUMNS: [&str; 4] = ["student", "question", "grader", "completed"];
+
+/// Where the regrade table's columns are by position, for when its headers don't match
+/// [`REGRADE_COLUMNS`]
+const REGRADE_POSITIONS: [&str; 5] = ["student", "sections", "question", "grader", "completed"];
+
+/// How many assignments' regrades to fetch at once by default, when fetching regrades for several
+/// assignments. Requests are still paced by the client, so this only overlaps waiting on
+/// Gradescope's responses.
+pub const DEFAULT_REGRADE_CONCURRENCY: NonZeroUsize = NonZeroUsize::new(4).unwrap();
+
+/// The minimum time between the starts of two requests from one client
+const REQUEST_INTERVAL: Duration = Duration::from_millis(1000);
+
 pub struct Client<State: ClientState> {
     client: HttpClient,
     creds: Creds,
+    strictness: Strictness,
+    login_retry: LoginRetry,
+    regrade_concurrency: NonZeroUsize,
+    stats: Mutex<RequestStats>,
+    warnings: Mutex<Vec<ParseWarning>>,
+    /// When the last request was sent, shared by concurrent requests so they go out one at a time
+    last_request: AsyncMutex<Instant>,
+    debug: DebugHook,
     _state: State,
 }
 
 impl<State: ClientState> Client<State> {
+    /// Sets how to handle pages that are missing elements we expect, such as after Gradescope
+    /// changes its markup
+    pub fn with_strictness(self, strictness: Strictness) -> Self {
+        Self { strictness, ..self }
+    }
+
+    /// Sets how many assignments' regrades to fetch at once when fetching regrades for several
+    /// assignments
+    pub fn with_regrade_concurrency(self, regrade_concurrency: NonZeroUsize) -> Self {
+        Self {
+            regrade_concurrency,
+            ..self
+        }
+    }
+
+    /// The email of the account this client uses
+    pub fn email(&self) -> &str {
+        self.creds.email()
+    }
+
+    /// Counts of the requests made so far by this client
+    pub fn request_stats(&self) -> RequestStats {
+        self.stats.lock().unwrap().clone()
+    }
+
+    fn record_request(&self, kind: RequestKind, bytes: usize) {
+        self.stats.lock().unwrap().record(kind, bytes as u64);
+    }
+
+    /// Takes the warnings about things skipped or only partially parsed since they were last taken.
+    /// They aren't printed as they happen, so callers should report them, such as at the end of a run.
+    pub fn take_warnings(&self) -> Vec<ParseWarning> {
+        std::mem::take(&mut self.warnings.lock().unwrap())
+    }
+
+    fn finish_lookup(&self, lookup: Lookup) -> Result<()> {
+        let warnings = lookup.finish()?;
+        self.warnings.lock().unwrap().extend(warnings);
+        Ok(())
+    }
+
+    /// Waits until at least [`REQUEST_INTERVAL`] after the last request. The lock is held while
+    /// waiting, so concurrent requests are sent one interval apart rather than all at once.
+    async fn wait_turn(&self) {
+        let mut last_request = self.last_request.lock().await;
+        sleep_until(*last_request + REQUEST_INTERVAL).await;
+        *last_request = Instant::now();
+    }
+
     async fn get_gs_html(&self, path: &str) -> Result<Html> {
-        let text = self.get_gs_response(path).await?.text().await?;
+        let text = self.get_gs_response(path).await?.text().await;
+        self.record_request(RequestKind::Page, text.as_ref().map_or(0, String::len));
+        let text = text?;
+        self.debug.log_body(path, &text);
         Ok(Html::parse_document(&text))
     }
 
     async fn get_gs_response(&self, path: &str) -> Result<Response> {
-        sleep(Duration::from_millis(1000)).await;
+        self.wait_turn().await;
 
         let url = gs_url(path);
         println!("sending request to {url}");
 
-        self.client
+        let response = self
+            .client
             .get(url)
             .send()
             .await
-            .context("Gradescope request failed")?
-            .error_for_status()
-            .context("Gradescope responded with an error")
+            .context("Gradescope request failed")?;
+        self.debug.log_response("GET", path, &response);
+
+        // the body of an error isn't read, but the request still counts
+        if let Err(err) = response.error_for_status_ref() {
+            self.record_request(RequestKind::Page, 0);
+            return Err(err).context("Gradescope responded with an error");
+        }
+        Ok(response)
     }
 }
 
@@ -82,18 +179,76 @@ impl Client<Init> {
             .redirect(Policy::none())
             .build()?;
 
+        let debug = DebugHook::from_env();
+
         // init cookies
-        client.get(BASE_URL).send().await?;
+        let response = client.get(BASE_URL).send().await?;
+        debug.log_response("GET", "/", &response);
+        let body = response.text().await?;
+        debug.log_body("/", &body);
+
+        let mut stats = RequestStats::default();
+        stats.record(RequestKind::Page, body.len() as u64);
 
         Ok(Self {
             client,
             creds,
+            strictness: Strictness::default(),
+            login_retry: LoginRetry::default(),
+            regrade_concurrency: DEFAULT_REGRADE_CONCURRENCY,
+            stats: Mutex::new(stats),
+            warnings: Mutex::default(),
+            last_request: AsyncMutex::new(Instant::now()),
+            debug,
             _state: Init,
         })
     }
 
+    /// Sets how to retry logging in after transient failures
+    pub fn with_login_retry(self, login_retry: LoginRetry) -> Self {
+        Self {
+            login_retry,
+            ..self
+        }
+    }
+
+    /// Logs in, retrying transient failures with backoff. Rejected credentials and lockouts fail
+    /// right away, since retrying them could lock the account out. The error can be downcast to a
+    /// [`LoginError`].
     pub async fn login(self) -> Result<Client<Auth>> {
-        let authenticity_token = self.get_authenticity_token().await?;
+        for attempt in 1.. {
+            match self.try_login().await {
+                Ok(()) => break,
+                Err(err) if err.is_transient() && attempt < self.login_retry.attempts() => {
+                    let delay = self.login_retry.delay(attempt);
+                    println!(
+                        "warning: login attempt {attempt} failed, retrying in {delay:?}: {err}"
+                    );
+                    sleep(delay).await;
+                }
+                Err(err) => return Err(err.into()),
+            }
+        }
+
+        Ok(Client {
+            client: self.client,
+            creds: self.creds,
+            strictness: self.strictness,
+            login_retry: self.login_retry,
+            regrade_concurrency: self.regrade_concurrency,
+            stats: self.stats,
+            warnings: self.warnings,
+            last_request: self.last_request,
+            debug: self.debug,
+            _state: Auth,
+        })
+    }
+
+    async fn try_login(&self) -> Result<(), LoginError> {
+        let authenticity_token = self
+            .get_authenticity_token()
+            .await
+            .map_err(LoginError::Transient)?;
 
         let login_data = {
             let mut login_data = HashMap::new();
@@ -107,99 +262,185 @@ impl Client<Init> {
             login_data
         };
 
+        self.wait_turn().await;
         let response = self
             .client
             .post(gs_url(LOGIN_PATH))
             .form(&login_data)
             .send()
-            .await?;
+            .await
+            .map_err(|err| LoginError::Transient(err.into()))?;
+        self.debug.log_response("POST", LOGIN_PATH, &response);
 
-        if response.status().is_redirection() {
-            Ok(Client {
-                client: self.client,
-                creds: self.creds,
-                _state: Auth,
-            })
+        let status = response.status();
+        let body = response.text().await;
+        self.record_request(RequestKind::Form, body.as_ref().map_or(0, String::len));
+        let body = body.map_err(|err| LoginError::Transient(err.into()))?;
+        self.debug.log_body(LOGIN_PATH, &body);
+
+        if status.is_redirection() {
+            return Ok(());
+        }
+        if status == StatusCode::TOO_MANY_REQUESTS {
+            return Err(LoginError::LockedOut);
+        }
+        // 422 means the authenticity token went stale, which another try fixes by fetching a new one
+        if status == StatusCode::UNPROCESSABLE_ENTITY || status.is_server_error() {
+            return Err(LoginError::Transient(anyhow!(
+                "Gradescope responded with {status}"
+            )));
+        }
+
+        // a rejected login renders the login page again, with a message saying why
+        if self.is_locked_out(&body) {
+            Err(LoginError::LockedOut)
         } else {
-            bail!("authentication failed")
+            Err(LoginError::WrongCredentials)
         }
     }
 
+    /// Whether the login page's alert says the account is locked, rather than that the email or
+    /// password is wrong
+    fn is_locked_out(&self, body: &str) -> bool {
+        let login_page = Html::parse_document(body);
+        let lookup = Lookup::new(Page::Login, self.strictness);
+
+        lookup
+            .optional(login_page.root_element(), &LOGIN_ALERT)
+            .is_some_and(|alert| text(alert).to_lowercase().contains("locked"))
+    }
+
     async fn get_authenticity_token(&self) -> Result<String> {
-        self.get_gs_html(LOGIN_PATH)
-            .await?
-            .select(&AUTHENTICITY_TOKEN)
-            .next()
+        let login_page = self.get_gs_html(LOGIN_PATH).await?;
+        let lookup = Lookup::new(Page::Login, self.strictness);
+
+        let token = lookup
+            .first(login_page.root_element(), &AUTHENTICITY_TOKEN)
             .and_then(|el| el.value().attr("value"))
-            .context("could not find `authenticity_token`")
-            .map(|token| token.to_owned())
+            .map(|token| token.to_owned());
+
+        self.finish_lookup(lookup)?;
+        token.context("could not find `authenticity_token`")
     }
 }
 
 impl Client<Auth> {
     pub async fn get_courses(&self) -> Result<(Vec<Course>, Vec<Course>)> {
         let account_page = self.get_gs_html(ACCOUNT_PATH).await?;
-        let course_list_headings = account_page
-            .select(&COURSE_LIST_HEADING)
-            .filter_map(|el| {
-                el.next_sibling()
-                    .and_then(ElementRef::wrap)
-                    .map(|list| (text(el), list))
-            })
-            .collect::<HashMap<_, _>>();
+        let lookup = Lookup::new(Page::Account, self.strictness);
+        let root = account_page.root_element();
+
+        // each course list follows its heading, so only look for headings if there are any lists
+        let course_list_headings = match lookup.first(root, &COURSE_LIST) {
+            Some(_) => lookup
+                .all(root, &COURSE_LIST_HEADING)
+                .into_iter()
+                .filter_map(|el| {
+                    el.next_sibling()
+                        .and_then(ElementRef::wrap)
+                        .map(|list| (text(el), list))
+                })
+                .collect(),
+            None => HashMap::new(),
+        };
 
         let instructor_course_list = course_list_headings.get("Instructor Courses");
         let student_course_list = course_list_headings.get("Student Courses");
 
-        let instructor_courses = instructor_course_list
-            .into_iter()
-            .flat_map(|list| Self::parse_courses(*list, Role::Instructor))
-            .collect();
-        let student_courses = student_course_list
-            .into_iter()
-            .flat_map(|list| Self::parse_courses(*list, Role::Student))
-            .collect();
+        let parse_courses = |list: Option<&ElementRef>, user_role| match list {
+            Some(list) => Self::parse_courses(&lookup, *list, user_role),
+            None => Ok(Vec::new()),
+        };
+        let instructor_courses = parse_courses(instructor_course_list, Role::Instructor)?;
+        let student_courses = parse_courses(student_course_list, Role::Student)?;
 
+        self.finish_lookup(lookup)?;
         Ok((instructor_courses, student_courses))
     }
 
-    fn parse_courses(list: ElementRef, user_role: Role) -> impl Iterator<Item = Course> + '_ {
-        list.select(&COURSE)
-            .filter_map(move |course_box| Self::parse_course(course_box, user_role))
+    fn parse_courses(lookup: &Lookup, list: ElementRef, user_role: Role) -> Result<Vec<Course>> {
+        let mut courses = Vec::new();
+
+        for course_box in lookup.all(list, &COURSE) {
+            let course = Self::parse_course(lookup, course_box, user_role).map_err(Into::into);
+            if let Some(course) = lookup.skip(course) {
+                if course.short_name().is_none() {
+                    lookup.warn(CourseWarning::MissingShortName {
+                        id: course.id().clone(),
+                        name: course.name().to_owned(),
+                    });
+                }
+                courses.push(course);
+            }
+        }
+
+        Ok(courses)
     }
 
-    fn parse_course(course_box: ElementRef, user_role: Role) -> Option<Course> {
-        let id = id_from_link(course_box)?;
-        let short_name = text(course_box.select(&COURSE_SHORT_NAME).next()?);
-        let name = text(course_box.select(&COURSE_NAME).next()?);
-        Some(Course::new(id, short_name, name, user_role))
+    /// Parses a course box, failing if it can't be used at all
+    fn parse_course(
+        lookup: &Lookup,
+        course_box: ElementRef,
+        user_role: Role,
+    ) -> Result<Course, CourseWarning> {
+        let id = id_from_link(course_box).map(CourseId::new).ok_or_else(|| {
+            CourseWarning::MissingId {
+                text: text(course_box).trim().to_owned(),
+            }
+        })?;
+        let name = lookup
+            .first(course_box, &COURSE_NAME)
+            .map(text)
+            .ok_or_else(|| CourseWarning::MissingName { id: id.clone() })?;
+        let short_name = lookup.optional(course_box, &COURSE_SHORT_NAME).map(text);
+
+        Ok(Course::new(id, short_name, name, user_role))
     }
 
     pub async fn get_assignments(&self, course: &Course) -> Result<Vec<Assignment>> {
         let assignments_page = self
             .get_gs_html(&gs_course_path(course, ASSIGNMENTS_COURSE_PATH))
             .await?;
+        let lookup = Lookup::new(Page::Assignments, self.strictness);
+
+        let rows = lookup
+            .first(assignments_page.root_element(), &ASSIGNMENT_TABLE)
+            .map(|table| lookup.all(table, &ASSIGNMENT_ROW))
+            .unwrap_or_default();
+        let mut assignments = Vec::new();
+        for row in rows {
+            let assignment = Self::parse_assignment(&lookup, row);
+            assignments.extend(lookup.skip(assignment));
+        }
 
-        let assignments = assignments_page
-            .select(&ASSIGNMENT_ROW)
-            .filter_map(Self::parse_assignment)
-            .collect();
-
+        self.finish_lookup(lookup)?;
         Ok(assignments)
     }
 
-    fn parse_assignment(row: ElementRef) -> Option<Assignment> {
-        let mut entries = row.select(&TD);
+    fn parse_assignment(lookup: &Lookup, row: ElementRef) -> Result<Assignment> {
+        let mut entries = lookup.all(row, &TD).into_iter();
 
-        let name_entry = entries.next()?;
-        let id = id_from_link(name_entry.select(&A).next()?)?;
+        let name_entry = entries.next().context("assignment row has no name entry")?;
         let name = AssignmentName::new(text(name_entry));
+        let id = lookup
+            .first(name_entry, &A)
+            .and_then(id_from_link)
+            .map(AssignmentId::new)
+            .with_context(|| format!("assignment \"{name}\" has no link"))?;
 
-        let points_entry = entries.next()?;
-        let points_value = text(points_entry).parse().ok()?;
-        let points = Points::new(points_value).ok()?;
-
-        Some(Assignment::new(id, name, points))
+        let points_entry = entries
+            .next()
+            .with_context(|| format!("assignment \"{name}\" has no points entry"))?;
+        let points_text = text(points_entry);
+        let points = points_text
+            .parse()
+            .map_err(anyhow::Error::from)
+            .and_then(Points::new)
+            .with_context(|| {
+                format!("assignment \"{name}\" has invalid points \"{points_text}\"")
+            })?;
+
+        Ok(Assignment::new(id, name, points))
     }
 
     pub async fn get_regrades(
@@ -214,24 +455,99 @@ impl Client<Auth> {
                 REGRADES_ASSIGNMENT_PATH,
             ))
             .await?;
+        let lookup = Lookup::new(Page::Regrades, self.strictness);
+
+        let regrades = Self::parse_regrades(&lookup, &regrade_page)?;
+
+        self.finish_lookup(lookup)?;
+        Ok(regrades)
+    }
+
+    fn parse_regrades(lookup: &Lookup, regrade_page: &Html) -> Result<Vec<Regrade>> {
+        let table = lookup.first(regrade_page.root_element(), &REGRADE_TABLE);
+        let mut columns: TableColumns = table
+            .map(|table| lookup.all(table, &REGRADE_HEADER))
+            .unwrap_or_default()
+            .into_iter()
+            .collect();
+        let rows = table
+            .map(|table| lookup.all(table, &REGRADE_ROW))
+            .unwrap_or_default();
+
+        if !rows.is_empty() && !columns.has_all(&REGRADE_COLUMNS) {
+            lookup.warn("regrade table headers didn't match, so reading its columns by position");
+            columns = TableColumns::positional(&REGRADE_POSITIONS);
+        }
 
-        let regrades = regrade_page
-            .select(&REGRADE_ROW)
-            .map(Self::parse_regrade)
-            .try_collect()?;
+        let mut regrades = Vec::new();
+        for (index, row) in rows.into_iter().enumerate() {
+            let regrade = Self::parse_regrade(lookup, &columns, row)
+                .with_context(|| format!("malformed regrade row {}", index + 1));
+            regrades.extend(lookup.skip_malformed(regrade)?);
+        }
 
         Ok(regrades)
     }
 
-    fn parse_regrade(row: ElementRef) -> Result<Regrade> {
-        let mut entries = row.select(&TD);
+    /// Gets the regrades for every assignment in the course, paired with their assignment
+    pub async fn get_all_regrades(&self, course: &Course) -> Result<Vec<(Assignment, Regrade)>> {
+        let all_regrades = self
+            .get_regrades_by_assignment(course)
+            .await?
+            .into_iter()
+            .flat_map(|(assignment, regrades)| {
+                regrades
+                    .into_iter()
+                    .map(move |regrade| (assignment.clone(), regrade))
+            })
+            .collect();
+
+        Ok(all_regrades)
+    }
 
-        let student_entry = entries.next().context("missing student entry")?;
+    /// Fetches everything about a course at once, for analysis that shouldn't depend on a live client
+    pub async fn get_snapshot(&self, course: &Course) -> Result<CourseSnapshot> {
+        let assignments = self.get_regrades_by_assignment(course).await?;
+        Ok(CourseSnapshot::new(course.clone(), assignments))
+    }
+
+    /// Gets the regrades for each of the given assignments, in the same order, fetching several
+    /// assignments' regrades at once as set by [`Client::with_regrade_concurrency`]
+    pub async fn get_regrades_for<'a>(
+        &self,
+        course: &Course,
+        assignments: impl IntoIterator<Item = &'a Assignment>,
+    ) -> Result<Vec<Vec<Regrade>>> {
+        stream::iter(assignments)
+            .map(|assignment| self.get_regrades(course, assignment))
+            .buffered(self.regrade_concurrency.get())
+            .try_collect()
+            .await
+    }
+
+    /// Gets every assignment in the course along with its regrades
+    async fn get_regrades_by_assignment(
+        &self,
+        course: &Course,
+    ) -> Result<Vec<(Assignment, Vec<Regrade>)>> {
+        let assignments = self.get_assignments(course).await?;
+        let regrades = self.get_regrades_for(course, &assignments).await?;
+        Ok(assignments.into_iter().zip(regrades).collect())
+    }
+
+    fn parse_regrade(lookup: &Lookup, columns: &TableColumns, row: ElementRef) -> Result<Regrade> {
+        let entries = lookup.all(row, &TD);
+
+        let student_entry = columns.cell(&entries, "student")?;
         let student_name = StudentName::new(text(student_entry));
 
-        let _sections_entry = entries.next().context("missing sections entry")?;
+        // only courses that use sections have this column, and a student may be in several
+        let sections = match columns.index("sections") {
+            Ok(_) => parse_sections(&text(columns.cell(&entries, "sections")?)),
+            Err(_) => Vec::new(),
+        };
 
-        let question_entry = entries.next().context("missing question entry")?;
+        let question_entry = columns.cell(&entries, "question")?;
         let question_entry_text = text(question_entry);
         let (question_number_text, question_title_text) = question_entry_text
             .split_once(':')
@@ -239,17 +555,15 @@ impl Client<Auth> {
         let question_number = QuestionNumber::new(question_number_text.to_owned());
         let question_title = QuestionTitle::new(question_title_text.to_owned());
 
-        let grader_entry = entries.next().context("missing grader entry")?;
+        let grader_entry = columns.cell(&entries, "grader")?;
         let grader_name = GraderName::new(text(grader_entry));
 
-        let completed_entry = entries.next().context("missing completed entry")?;
+        let completed_entry = columns.cell(&entries, "completed")?;
         let completed = completed_entry.has_children();
 
-        let link_entry = entries.next().context("missing link entry")?;
-        let url_text = link_entry
-            .select(&A)
-            .next()
-            .context("missing link element")?
+        let url_text = lookup
+            .first(row, &REGRADE_LINK)
+            .context("missing review link")?
             .value()
             .attr("href")
             .context("missing href attribute")?;
@@ -257,6 +571,7 @@ impl Client<Auth> {
 
         Ok(Regrade::new(
             student_name,
+            sections,
             question_number,
             question_title,
             grader_name,
@@ -266,9 +581,125 @@ impl Client<Auth> {
     }
 }
 
+fn parse_sections(text: &str) -> Vec<Section> {
+    text.split(',')
+        .map(str::trim)
+        .filter(|section| !section.is_empty())
+        .map(|section| Section::new(section.to_owned()))
+        .collect()
+}
+
 pub struct Init;
 pub struct Auth;
 
 pub trait ClientState {}
 impl ClientState for Init {}
 impl ClientState for Auth {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(page: &str) -> Result<Vec<Regrade>> {
+        let page = Html::parse_document(page);
+        let lookup = Lookup::new(Page::Regrades, Strictness::Strict);
+        let regrades = Client::<Auth>::parse_regrades(&lookup, &page)?;
+        lookup.finish()?;
+        Ok(regrades)
+    }
+
+    const LINK: &str = "/courses/1/questions/2/submissions/3/grade?regrade_request_id=4";
+
+    fn check(regrade: &Regrade, sections: &[&str], completed: bool) {
+        assert_eq!(regrade.student_name().as_str(), "Ada Lovelace");
+        let expected_sections = sections
+            .iter()
+            .map(|section| Section::new((*section).to_owned()))
+            .collect::<Vec<_>>();
+        assert_eq!(regrade.sections(), expected_sections);
+        assert_eq!(regrade.question_number().as_str(), "1.b");
+        assert_eq!(regrade.question_title().as_str(), " Proofs");
+        assert_eq!(regrade.grader_name().as_str(), "Grace Hopper");
+        assert_eq!(regrade.completed(), completed);
+        assert_eq!(regrade.url().as_str(), format!("{BASE_URL}{LINK}"));
+    }
+
+    #[test]
+    fn parses_regrades_by_header() {
+        let regrades = parse(&format!(
+            r#"
+            <table class="table js-regradeRequestsTable">
+                <thead><tr>
+                    <th>Student</th><th>Sections</th><th>Question</th><th>Grader</th><th>Completed</th>
+                    <th><span class="sr-only">Review</span></th>
+                </tr></thead>
+                <tbody><tr>
+                    <td>Ada Lovelace</td><td>012, 013</td><td>1.b: Proofs</td><td>Grace Hopper</td>
+                    <td><i class="fa fa-check"></i></td><td><a href="{LINK}">Review</a></td>
+                </tr></tbody>
+            </table>
+            "#
+        ))
+        .unwrap();
+
+        assert_eq!(regrades.len(), 1);
+        check(&regrades[0], &["012", "013"], true);
+    }
+
+    #[test]
+    fn parses_reordered_regrade_columns() {
+        let regrades = parse(&format!(
+            r#"
+            <table class="js-regradeRequestsTable">
+                <thead><tr><th></th><th>Completed</th><th>Grader</th><th>Question</th><th>Student</th></tr></thead>
+                <tbody><tr>
+                    <td><a href="{LINK}">Review</a></td><td></td><td>Grace Hopper</td>
+                    <td>1.b: Proofs</td><td>Ada Lovelace</td>
+                </tr></tbody>
+            </table>
+            "#
+        ))
+        .unwrap();
+
+        assert_eq!(regrades.len(), 1);
+        check(&regrades[0], &[], false);
+    }
+
+    #[test]
+    fn parses_regrades_by_position_without_headers() {
+        let regrades = parse(&format!(
+            r#"
+            <table class="js-regradeRequestsTable">
+                <tbody><tr>
+                    <td>Ada Lovelace</td><td>012</td><td>1.b: Proofs</td><td>Grace Hopper</td>
+                    <td></td><td><a href="{LINK}">Review</a></td>
+                </tr></tbody>
+            </table>
+            "#
+        ))
+        .unwrap();
+
+        assert_eq!(regrades.len(), 1);
+        check(&regrades[0], &["012"], false);
+    }
+
+    #[test]
+    fn malformed_regrade_row_fails_unless_tolerant() {
+        let page = Html::parse_document(
+            r#"
+            <table class="js-regradeRequestsTable">
+                <thead><tr><th>Student</th><th>Question</th><th>Grader</th><th>Completed</th><th></th></tr></thead>
+                <tbody><tr><td>Ada Lovelace</td><td>no colon</td><td>Grace Hopper</td><td></td><td></td></tr></tbody>
+            </table>
+            "#,
+        );
+
+        let lookup = Lookup::new(Page::Regrades, Strictness::Lenient);
+        assert!(Client::<Auth>::parse_regrades(&lookup, &page).is_err());
+
+        let lookup = Lookup::new(Page::Regrades, Strictness::Tolerant);
+        let regrades = Client::<Auth>::parse_regrades(&lookup, &page).unwrap();
+        assert!(regrades.is_empty());
+        assert_eq!(lookup.finish().unwrap().len(), 1);
+    }
+}
@@ -1,15 +1,21 @@
-use anyhow::{Context, Result};
+use std::fmt;
+use std::str::FromStr;
 
-#[derive(Debug, Clone)]
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::util::string_id;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Course {
-    id: String,
-    short_name: String,
+    id: CourseId,
+    short_name: Option<String>,
     name: String,
     user_role: Role,
 }
 
 impl Course {
-    pub fn new(id: String, short_name: String, name: String, user_role: Role) -> Self {
+    pub fn new(id: CourseId, short_name: Option<String>, name: String, user_role: Role) -> Self {
         Self {
             id,
             short_name,
@@ -18,12 +24,13 @@ impl Course {
         }
     }
 
-    pub fn id(&self) -> &str {
+    pub fn id(&self) -> &CourseId {
         &self.id
     }
 
-    pub fn short_name(&self) -> &str {
-        &self.short_name
+    /// Some courses, such as workshops, have no short name
+    pub fn short_name(&self) -> Option<&str> {
+        self.short_name.as_deref()
     }
 
     pub fn name(&self) -> &str {
@@ -34,20 +41,85 @@ impl Course {
         self.user_role
     }
 
-    pub fn find_by_short_name(
-        name: &str,
-        courses: impl IntoIterator<Item = Self>,
-    ) -> Result<Course> {
-        let pred = |course: &Course| course.short_name() == name;
+    /// Finds a course by short name, full name, or id or URL, preferring matches in that order
+    pub fn find(selector: &str, courses: impl IntoIterator<Item = Self>) -> Result<Course> {
+        let mut courses: Vec<_> = courses.into_iter().collect();
+        let id = selector.parse::<CourseId>().ok();
+
         courses
-            .into_iter()
-            .find(pred)
-            .with_context(|| format!("could not find course with short name \"{name}\""))
+            .iter()
+            .position(|course| course.short_name() == Some(selector))
+            .or_else(|| courses.iter().position(|course| course.name() == selector))
+            .or_else(|| {
+                courses
+                    .iter()
+                    .position(|course| Some(course.id()) == id.as_ref())
+            })
+            .map(|index| courses.swap_remove(index))
+            .with_context(|| format!("could not find course \"{selector}\""))
+    }
+}
+
+string_id! {
+    /// Parses from a bare id or any Gradescope URL within the course
+    CourseId, "courses"
+}
+
+/// Something wrong with a course on the account page
+#[derive(Debug, Clone)]
+pub enum CourseWarning {
+    /// It has no link to the course, and so no id
+    MissingId {
+        text: String,
+    },
+    MissingName {
+        id: CourseId,
+    },
+    /// The course can still be used, since it's optional
+    MissingShortName {
+        id: CourseId,
+        name: String,
+    },
+}
+
+impl std::error::Error for CourseWarning {}
+
+impl fmt::Display for CourseWarning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::MissingId { text } => write!(f, "course \"{text}\" has no link"),
+            Self::MissingName { id } => write!(f, "course {id} has no name"),
+            Self::MissingShortName { id, name } => {
+                write!(f, "course \"{name}\" ({id}) has no short name")
+            }
+        }
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Role {
     Student,
     Instructor,
 }
+
+impl fmt::Display for Role {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Self::Student => "student",
+            Self::Instructor => "instructor",
+        };
+        name.fmt(f)
+    }
+}
+
+impl FromStr for Role {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "student" => Ok(Self::Student),
+            "instructor" => Ok(Self::Instructor),
+            _ => bail!("unknown role \"{s}\""),
+        }
+    }
+}
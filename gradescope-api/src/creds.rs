@@ -13,6 +13,24 @@ impl Creds {
         Ok(Self::new(username, password))
     }
 
+    /// Reads every account configured in the environment: `EMAIL` and `GS_PASSWORD`, then `EMAIL_2`
+    /// and `GS_PASSWORD_2`, and so on, stopping at the first missing `EMAIL_<n>`
+    pub fn all_from_env() -> Result<Vec<Self>, VarError> {
+        let mut all = vec![Self::from_env()?];
+
+        for n in 2.. {
+            let email = match env::var(format!("EMAIL_{n}")) {
+                Ok(email) => email,
+                Err(VarError::NotPresent) => break,
+                Err(err) => return Err(err),
+            };
+            let password = env::var(format!("GS_PASSWORD_{n}"))?;
+            all.push(Self::new(email, password));
+        }
+
+        Ok(all)
+    }
+
     pub fn new(email: String, password: String) -> Self {
         Self { email, password }
     }
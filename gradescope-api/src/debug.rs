@@ -0,0 +1,77 @@
+//! Opt-in logging of requests and responses, for debugging issues such as failed logins without
+//! modifying library code. Controlled by environment variables:
+//! - `GS_DEBUG`: if set, logs each response's status, redirect location, and cookies, with cookie
+//!   values redacted
+//! - `GS_DEBUG_BODIES`: comma-separated path prefixes, such as `/login,/account`, whose response
+//!   bodies are also logged
+
+use std::env;
+
+use reqwest::header::{LOCATION, SET_COOKIE};
+use reqwest::Response;
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct DebugHook {
+    enabled: bool,
+    body_paths: Vec<String>,
+}
+
+impl DebugHook {
+    pub fn from_env() -> Self {
+        let body_paths: Vec<_> = env::var("GS_DEBUG_BODIES")
+            .map(|paths| {
+                paths
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|path| !path.is_empty())
+                    .map(ToOwned::to_owned)
+                    .collect()
+            })
+            .unwrap_or_default();
+        let enabled = env::var_os("GS_DEBUG").is_some() || !body_paths.is_empty();
+
+        Self {
+            enabled,
+            body_paths,
+        }
+    }
+
+    pub fn log_response(&self, method: &str, path: &str, response: &Response) {
+        if !self.enabled {
+            return;
+        }
+
+        println!("debug: {method} {path} -> {}", response.status());
+
+        let headers = response.headers();
+        if let Some(location) = headers.get(LOCATION) {
+            println!("debug:   redirect to {location:?}");
+        }
+        for cookie in headers.get_all(SET_COOKIE) {
+            let cookie = String::from_utf8_lossy(cookie.as_bytes());
+            println!("debug:   set-cookie {}", redact_cookie(&cookie));
+        }
+    }
+
+    pub fn log_body(&self, path: &str, body: &str) {
+        if self
+            .body_paths
+            .iter()
+            .any(|prefix| path.starts_with(prefix))
+        {
+            println!("debug: body of {path}:\n{body}");
+        }
+    }
+}
+
+/// Hides a cookie's value while keeping its name and attributes
+fn redact_cookie(cookie: &str) -> String {
+    let (pair, attributes) = cookie.split_once(';').unwrap_or((cookie, ""));
+    let name = pair.split_once('=').map_or(pair, |(name, _)| name);
+
+    if attributes.is_empty() {
+        format!("{name}=*****")
+    } else {
+        format!("{name}=*****;{attributes}")
+    }
+}
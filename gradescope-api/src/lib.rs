@@ -1,8 +1,18 @@
 pub mod assignment;
+#[cfg(feature = "blocking")]
+pub mod blocking;
 pub mod client;
 pub mod course;
 pub mod creds;
+pub mod login;
+pub mod pool;
+pub mod prelude;
 pub mod regrade;
+pub mod selector;
+pub mod snapshot;
+pub mod stats;
 pub mod types;
 
+mod debug;
+mod table;
 mod util;
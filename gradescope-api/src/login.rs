@@ -0,0 +1,74 @@
+//! Retrying logins without risking an account lockout: only transient failures, such as server
+//! errors, are retried, while rejected credentials fail right away.
+
+use std::fmt;
+use std::time::Duration;
+
+/// How to retry logging in after a transient failure, doubling the delay after each attempt
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LoginRetry {
+    attempts: u32,
+    initial_delay: Duration,
+}
+
+impl LoginRetry {
+    /// `attempts` includes the first attempt, so 1 means never retry
+    pub fn new(attempts: u32, initial_delay: Duration) -> Self {
+        Self {
+            attempts: attempts.max(1),
+            initial_delay,
+        }
+    }
+
+    pub fn never() -> Self {
+        Self::new(1, Duration::ZERO)
+    }
+
+    pub fn attempts(self) -> u32 {
+        self.attempts
+    }
+
+    /// The delay before retrying after the given failed attempt, counting from 1
+    pub fn delay(self, attempt: u32) -> Duration {
+        self.initial_delay
+            .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)))
+    }
+}
+
+impl Default for LoginRetry {
+    fn default() -> Self {
+        Self::new(3, Duration::from_secs(5))
+    }
+}
+
+/// Why logging in failed. Only [`LoginError::Transient`] is retried.
+#[derive(Debug)]
+pub enum LoginError {
+    /// Gradescope rejected the email or password
+    WrongCredentials,
+    /// Gradescope is refusing logins for now, such as after too many attempts
+    LockedOut,
+    /// Something that may work on another try, such as a network or server error
+    Transient(anyhow::Error),
+}
+
+impl LoginError {
+    pub fn is_transient(&self) -> bool {
+        matches!(self, Self::Transient(_))
+    }
+}
+
+impl fmt::Display for LoginError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::WrongCredentials => write!(f, "authentication failed: wrong email or password"),
+            Self::LockedOut => write!(
+                f,
+                "authentication failed: Gradescope is refusing logins for this account, so not retrying"
+            ),
+            Self::Transient(err) => write!(f, "authentication failed: {err:#}"),
+        }
+    }
+}
+
+impl std::error::Error for LoginError {}
@@ -0,0 +1,165 @@
+//! Spreading large read-only jobs across several staff accounts, each with its own session and
+//! request budget.
+
+use std::future::Future;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use anyhow::{bail, Context, Result};
+use reqwest::StatusCode;
+
+use crate::assignment::Assignment;
+use crate::client::{Auth, Client};
+use crate::course::Course;
+use crate::creds::Creds;
+use crate::login::LoginError;
+use crate::regrade::Regrade;
+use crate::stats::RequestStats;
+
+pub struct ClientPool {
+    clients: Vec<Client<Auth>>,
+    next: AtomicUsize,
+}
+
+impl ClientPool {
+    pub async fn from_env() -> Result<Self> {
+        let creds = Creds::all_from_env()?;
+        Self::login(creds).await
+    }
+
+    /// Logs in to every account. An account that fails to log in is skipped with a warning so that it
+    /// doesn't stop the whole job, but at least one account must succeed.
+    pub async fn login(creds: impl IntoIterator<Item = Creds>) -> Result<Self> {
+        let mut clients = Vec::new();
+
+        for creds in creds {
+            let email = creds.email().to_owned();
+            match Self::login_one(creds).await {
+                Ok(client) => clients.push(client),
+                Err(err) => println!("warning: skipping account {email}: {err:#}"),
+            }
+        }
+
+        if clients.is_empty() {
+            bail!("could not log in to any account");
+        }
+
+        Ok(Self {
+            clients,
+            next: AtomicUsize::new(0),
+        })
+    }
+
+    async fn login_one(creds: Creds) -> Result<Client<Auth>> {
+        Client::new(creds).await?.login().await
+    }
+
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> usize {
+        self.clients.len()
+    }
+
+    /// Takes the next client in turn
+    pub fn next(&self) -> &Client<Auth> {
+        &self.clients[self.next_index()]
+    }
+
+    fn next_index(&self) -> usize {
+        self.next.fetch_add(1, Ordering::Relaxed) % self.clients.len()
+    }
+
+    /// Runs a request on the next client in turn, moving on to the other clients if it fails because
+    /// of the account or connection, so that one account having problems doesn't fail the request.
+    /// Other failures, such as a page that doesn't parse, are returned right away, since they would
+    /// only fail again on every account.
+    pub async fn try_each<'a, T, Fut>(&'a self, f: impl Fn(&'a Client<Auth>) -> Fut) -> Result<T>
+    where
+        Fut: Future<Output = Result<T>>,
+    {
+        let mut last_err = None;
+
+        // Every other client is tried once, even if other tasks are taking clients concurrently
+        let start = self.next_index();
+        for offset in 0..self.clients.len() {
+            let client = &self.clients[(start + offset) % self.clients.len()];
+            match f(client).await {
+                Ok(value) => return Ok(value),
+                Err(err) if is_account_error(&err) => {
+                    println!(
+                        "warning: request failed for account {}: {err:#}",
+                        client.email()
+                    );
+                    last_err = Some(err);
+                }
+                Err(err) => return Err(err),
+            }
+        }
+
+        Err(last_err.expect("pool should have at least one client"))
+            .context("request failed for every account")
+    }
+
+    pub async fn get_assignments(&self, course: &Course) -> Result<Vec<Assignment>> {
+        self.try_each(|client| client.get_assignments(course)).await
+    }
+
+    pub async fn get_regrades(
+        &self,
+        course: &Course,
+        assignment: &Assignment,
+    ) -> Result<Vec<Regrade>> {
+        self.try_each(|client| client.get_regrades(course, assignment))
+            .await
+    }
+
+    /// Request counts for each account, by email
+    pub fn request_stats(&self) -> impl Iterator<Item = (&str, RequestStats)> {
+        self.clients
+            .iter()
+            .map(|client| (client.email(), client.request_stats()))
+    }
+}
+
+/// Whether a request may have failed because of its account's session or connection, rather than
+/// because of what was requested
+fn is_account_error(err: &anyhow::Error) -> bool {
+    err.chain().any(|cause| {
+        if cause.is::<LoginError>() {
+            return true;
+        }
+        match cause
+            .downcast_ref::<reqwest::Error>()
+            .map(reqwest::Error::status)
+        {
+            // no status means the request itself failed, such as a timeout
+            Some(None) => true,
+            Some(Some(status)) => {
+                matches!(
+                    status,
+                    StatusCode::UNAUTHORIZED
+                        | StatusCode::FORBIDDEN
+                        | StatusCode::TOO_MANY_REQUESTS
+                ) || status.is_server_error()
+            }
+            None => false,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::anyhow;
+
+    use super::*;
+
+    #[test]
+    fn login_errors_are_account_errors() {
+        let err = anyhow::Error::new(LoginError::LockedOut).context("request failed");
+        assert!(is_account_error(&err));
+    }
+
+    #[test]
+    fn parse_errors_are_not_account_errors() {
+        let err = anyhow!("couldn't split question entry").context("malformed regrade row 1");
+        assert!(!is_account_error(&err));
+    }
+}
@@ -0,0 +1,16 @@
+//! The types most programs need, so they can be imported all at once with
+//! `use gradescope_api::prelude::*;`
+
+pub use crate::assignment::{Assignment, AssignmentId, AssignmentName};
+pub use crate::client::{Auth, Client, Init};
+pub use crate::course::{Course, CourseId, Role};
+pub use crate::creds::Creds;
+pub use crate::login::{LoginError, LoginRetry};
+pub use crate::pool::ClientPool;
+pub use crate::regrade::Regrade;
+pub use crate::selector::{ParseWarning, SelectorStats, Strictness};
+pub use crate::snapshot::CourseSnapshot;
+pub use crate::stats::RequestStats;
+pub use crate::types::{
+    GraderName, Points, QuestionNumber, QuestionPart, QuestionTitle, Section, StudentName,
+};
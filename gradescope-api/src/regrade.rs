@@ -1,10 +1,13 @@
+use serde::{Deserialize, Serialize};
 use url::Url;
 
-use crate::types::{GraderName, QuestionNumber, QuestionTitle, StudentName};
+use crate::types::{GraderName, QuestionNumber, QuestionTitle, Section, StudentName};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Regrade {
     student_name: StudentName,
+    #[serde(default)]
+    sections: Vec<Section>,
     question_number: QuestionNumber,
     question_title: QuestionTitle,
     grader_name: GraderName,
@@ -15,6 +18,7 @@ pub struct Regrade {
 impl Regrade {
     pub fn new(
         student_name: StudentName,
+        sections: Vec<Section>,
         question_number: QuestionNumber,
         question_title: QuestionTitle,
         grader_name: GraderName,
@@ -23,6 +27,7 @@ impl Regrade {
     ) -> Self {
         Self {
             student_name,
+            sections,
             question_number,
             question_title,
             grader_name,
@@ -35,6 +40,15 @@ impl Regrade {
         &self.student_name
     }
 
+    /// The student's sections, which are empty if the course doesn't use sections
+    pub fn sections(&self) -> &[Section] {
+        &self.sections
+    }
+
+    pub fn in_section(&self, section: &Section) -> bool {
+        self.sections.contains(section)
+    }
+
     pub fn question_number(&self) -> &QuestionNumber {
         &self.question_number
     }
@@ -0,0 +1,270 @@
+//! Telemetry for the CSS selectors used to scrape Gradescope, so that changes to its markup show up
+//! as selector misses before they show up as hard failures.
+
+use std::cell::RefCell;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::ops::Deref;
+use std::sync::Mutex;
+
+use anyhow::{bail, Result};
+use itertools::Itertools;
+use lazy_static::lazy_static;
+use scraper::{ElementRef, Selector};
+
+lazy_static! {
+    static ref STATS: Mutex<BTreeMap<(Page, &'static str), SelectorCount>> = Mutex::default();
+}
+
+/// A selector that knows its own name, for reporting
+#[derive(Debug)]
+pub struct NamedSelector {
+    name: &'static str,
+    selector: Selector,
+}
+
+impl NamedSelector {
+    pub(crate) fn parse(name: &'static str, selectors: &str) -> Self {
+        let selector = Selector::parse(selectors).unwrap();
+        Self { name, selector }
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+}
+
+impl Deref for NamedSelector {
+    type Target = Selector;
+
+    fn deref(&self) -> &Self::Target {
+        &self.selector
+    }
+}
+
+/// The kinds of Gradescope pages that get scraped
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Page {
+    Login,
+    Account,
+    Assignments,
+    Regrades,
+}
+
+impl fmt::Display for Page {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Self::Login => "login",
+            Self::Account => "account",
+            Self::Assignments => "assignments",
+            Self::Regrades => "regrades",
+        };
+        name.fmt(f)
+    }
+}
+
+/// How to handle pages that aren't what we expect.
+///
+/// Items that are expected to fail to parse sometimes, such as assignments without points, are
+/// skipped with a warning in every mode. The "lenient" mode that also skips malformed items, such
+/// as regrade rows, rather than failing the whole request is [`Strictness::Tolerant`];
+/// [`Strictness::Lenient`] only tolerates selector misses, so that the default keeps failing on
+/// malformed rows as before.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Strictness {
+    /// Fail the request when a selector unexpectedly matches nothing or an item is malformed
+    Strict,
+    /// Skip whatever couldn't be found, with a warning, but fail the request when an item is
+    /// malformed
+    #[default]
+    Lenient,
+    /// Skip whatever couldn't be found and any malformed items, with a warning, returning partial
+    /// results
+    Tolerant,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SelectorCount {
+    hits: u64,
+    misses: u64,
+}
+
+impl SelectorCount {
+    pub fn hits(self) -> u64 {
+        self.hits
+    }
+
+    pub fn misses(self) -> u64 {
+        self.misses
+    }
+}
+
+/// Hit and miss counts for every selector used so far, by page
+#[derive(Debug, Clone, Default)]
+pub struct SelectorStats {
+    counts: BTreeMap<(Page, &'static str), SelectorCount>,
+}
+
+impl SelectorStats {
+    pub fn snapshot() -> Self {
+        let counts = STATS.lock().unwrap().clone();
+        Self { counts }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Page, &'static str, SelectorCount)> + '_ {
+        self.counts
+            .iter()
+            .map(|(&(page, name), &count)| (page, name, count))
+    }
+
+    pub fn has_misses(&self) -> bool {
+        self.counts.values().any(|count| count.misses > 0)
+    }
+}
+
+impl fmt::Display for SelectorStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (page, name, count) in self.iter() {
+            writeln!(
+                f,
+                "{page} page, {name}: {} hit(s), {} miss(es)",
+                count.hits, count.misses
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Something on a page that was skipped or only partially parsed
+#[derive(Debug, Clone)]
+pub struct ParseWarning {
+    page: Page,
+    message: String,
+}
+
+impl ParseWarning {
+    pub fn page(&self) -> Page {
+        self.page
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for ParseWarning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} page: {}", self.page, self.message)
+    }
+}
+
+/// Looks up elements on a single page, recording hits and misses
+#[derive(Debug)]
+pub(crate) struct Lookup {
+    page: Page,
+    strictness: Strictness,
+    misses: RefCell<BTreeSet<&'static str>>,
+    warnings: RefCell<Vec<ParseWarning>>,
+}
+
+impl Lookup {
+    pub fn new(page: Page, strictness: Strictness) -> Self {
+        Self {
+            page,
+            strictness,
+            misses: RefCell::default(),
+            warnings: RefCell::default(),
+        }
+    }
+
+    /// Finds the first match for an element that is expected to exist
+    pub fn first<'a>(
+        &self,
+        el: ElementRef<'a>,
+        selector: &NamedSelector,
+    ) -> Option<ElementRef<'a>> {
+        let found = el.select(selector).next();
+        self.record(selector, found.is_some());
+        found
+    }
+
+    /// Finds the first match for an element that may legitimately be missing, so that not finding it
+    /// is not counted as a miss
+    pub fn optional<'a>(
+        &self,
+        el: ElementRef<'a>,
+        selector: &NamedSelector,
+    ) -> Option<ElementRef<'a>> {
+        let found = el.select(selector).next();
+        if found.is_some() {
+            self.record(selector, true);
+        }
+        found
+    }
+
+    /// Finds all matches for a list of elements. Lists can legitimately be empty, so finding nothing
+    /// is not counted as a miss. To still notice when the list's markup changes, look up its
+    /// container with [`Lookup::first`] and search only within that.
+    pub fn all<'a>(&self, el: ElementRef<'a>, selector: &NamedSelector) -> Vec<ElementRef<'a>> {
+        let found = el.select(selector).collect_vec();
+        if !found.is_empty() {
+            self.record(selector, true);
+        }
+        found
+    }
+
+    /// Notes something that was parsed, but not completely
+    pub fn warn(&self, message: impl fmt::Display) {
+        self.warnings.borrow_mut().push(ParseWarning {
+            page: self.page,
+            message: message.to_string(),
+        });
+    }
+
+    /// Handles an item that is expected to fail to parse sometimes, such as an assignment without
+    /// points, by skipping it with a warning
+    pub fn skip<T>(&self, item: Result<T>) -> Option<T> {
+        item.map_err(|err| self.warn(format_args!("{err:#} (skipped)")))
+            .ok()
+    }
+
+    /// Handles an item, such as a table row, that should always parse. It fails the request unless
+    /// tolerant, in which case it's skipped with a warning so that one malformed item doesn't fail
+    /// the whole page.
+    pub fn skip_malformed<T>(&self, item: Result<T>) -> Result<Option<T>> {
+        match self.strictness {
+            Strictness::Strict | Strictness::Lenient => item.map(Some),
+            Strictness::Tolerant => Ok(self.skip(item)),
+        }
+    }
+
+    fn record(&self, selector: &NamedSelector, hit: bool) {
+        let mut stats = STATS.lock().unwrap();
+        let count = stats.entry((self.page, selector.name)).or_default();
+        if hit {
+            count.hits += 1;
+        } else {
+            count.misses += 1;
+            self.misses.borrow_mut().insert(selector.name);
+        }
+    }
+
+    /// Checks for misses on this page, failing if strict, and returns the warnings for the page
+    pub fn finish(self) -> Result<Vec<ParseWarning>> {
+        let misses = self.misses.take();
+        if !misses.is_empty() {
+            let misses = misses.into_iter().join(", ");
+            match self.strictness {
+                Strictness::Strict => bail!(
+                    "selector(s) {misses} matched nothing on the {} page; Gradescope's markup may have changed",
+                    self.page
+                ),
+                Strictness::Lenient | Strictness::Tolerant => self.warn(format_args!(
+                    "selector(s) {misses} matched nothing; skipped what couldn't be parsed"
+                )),
+            }
+        }
+
+        Ok(self.warnings.into_inner())
+    }
+}
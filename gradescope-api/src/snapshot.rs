@@ -0,0 +1,95 @@
+//! Course data fetched at one point in time, which can be saved to and loaded from disk so that
+//! reports are reproducible and don't need a live client.
+
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::path::Path;
+use std::time::SystemTime;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::assignment::{Assignment, AssignmentId};
+use crate::course::Course;
+use crate::regrade::Regrade;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CourseSnapshot {
+    course: Course,
+    fetched_at: SystemTime,
+    assignments: Vec<AssignmentSnapshot>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct AssignmentSnapshot {
+    assignment: Assignment,
+    regrades: Vec<Regrade>,
+}
+
+impl CourseSnapshot {
+    /// Makes a snapshot fetched now
+    pub fn new(
+        course: Course,
+        assignments: impl IntoIterator<Item = (Assignment, Vec<Regrade>)>,
+    ) -> Self {
+        let assignments = assignments
+            .into_iter()
+            .map(|(assignment, regrades)| AssignmentSnapshot {
+                assignment,
+                regrades,
+            })
+            .collect();
+
+        Self {
+            course,
+            fetched_at: SystemTime::now(),
+            assignments,
+        }
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let file = File::open(path)
+            .with_context(|| format!("could not open snapshot {}", path.display()))?;
+        serde_json::from_reader(BufReader::new(file))
+            .with_context(|| format!("could not read snapshot {}", path.display()))
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let file = File::create(path)
+            .with_context(|| format!("could not create snapshot {}", path.display()))?;
+        serde_json::to_writer_pretty(BufWriter::new(file), self)
+            .with_context(|| format!("could not write snapshot {}", path.display()))
+    }
+
+    pub fn course(&self) -> &Course {
+        &self.course
+    }
+
+    pub fn fetched_at(&self) -> SystemTime {
+        self.fetched_at
+    }
+
+    pub fn assignments(&self) -> impl Iterator<Item = &Assignment> {
+        self.assignments.iter().map(|snapshot| &snapshot.assignment)
+    }
+
+    /// The regrades for an assignment, or `None` if the assignment isn't in the snapshot
+    pub fn regrades(&self, id: &AssignmentId) -> Option<&[Regrade]> {
+        self.assignments
+            .iter()
+            .find(|snapshot| snapshot.assignment.id() == id)
+            .map(|snapshot| snapshot.regrades.as_slice())
+    }
+
+    /// Every regrade in the course, along with its assignment
+    pub fn all_regrades(&self) -> impl Iterator<Item = (&Assignment, &Regrade)> {
+        self.assignments.iter().flat_map(|snapshot| {
+            snapshot
+                .regrades
+                .iter()
+                .map(|regrade| (&snapshot.assignment, regrade))
+        })
+    }
+}
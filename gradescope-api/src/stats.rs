@@ -0,0 +1,85 @@
+//! Accounting of the requests a client makes, to keep scraping within a respectful budget and help
+//! explain differences in runtime.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// The kinds of requests sent to Gradescope
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum RequestKind {
+    /// Fetching an HTML page
+    Page,
+    /// Submitting a form, such as logging in
+    Form,
+}
+
+impl fmt::Display for RequestKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Self::Page => "page",
+            Self::Form => "form",
+        };
+        name.fmt(f)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RequestCount {
+    requests: u64,
+    bytes: u64,
+}
+
+impl RequestCount {
+    pub fn requests(self) -> u64 {
+        self.requests
+    }
+
+    /// Total size of the response bodies that were read
+    pub fn bytes(self) -> u64 {
+        self.bytes
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct RequestStats {
+    counts: BTreeMap<RequestKind, RequestCount>,
+}
+
+impl RequestStats {
+    pub(crate) fn record(&mut self, kind: RequestKind, bytes: u64) {
+        let count = self.counts.entry(kind).or_default();
+        count.requests += 1;
+        count.bytes += bytes;
+    }
+
+    pub fn get(&self, kind: RequestKind) -> RequestCount {
+        self.counts.get(&kind).copied().unwrap_or_default()
+    }
+
+    pub fn total(&self) -> RequestCount {
+        self.counts
+            .values()
+            .fold(RequestCount::default(), |total, count| RequestCount {
+                requests: total.requests + count.requests,
+                bytes: total.bytes + count.bytes,
+            })
+    }
+}
+
+impl fmt::Display for RequestStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (kind, count) in &self.counts {
+            writeln!(
+                f,
+                "{kind}: {} request(s), {} byte(s)",
+                count.requests, count.bytes
+            )?;
+        }
+        let total = self.total();
+        write!(
+            f,
+            "total: {} request(s), {} byte(s)",
+            total.requests, total.bytes
+        )
+    }
+}
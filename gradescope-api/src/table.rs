@@ -0,0 +1,229 @@
+//! Reading HTML tables by column header rather than by position, so that columns Gradescope adds or
+//! reorders don't silently shift data.
+
+use anyhow::{Context, Result};
+use scraper::ElementRef;
+
+use crate::util::text;
+
+#[derive(Debug, Clone)]
+pub struct TableColumns {
+    headers: Vec<String>,
+}
+
+impl<'a> FromIterator<ElementRef<'a>> for TableColumns {
+    fn from_iter<T: IntoIterator<Item = ElementRef<'a>>>(headers: T) -> Self {
+        let headers = headers
+            .into_iter()
+            .map(|header| normalize(&text(header)))
+            .collect();
+        Self { headers }
+    }
+}
+
+impl TableColumns {
+    /// Columns at fixed positions, named in order, for tables whose headers can't be used
+    pub fn positional(names: &[&str]) -> Self {
+        let headers = names.iter().map(|name| normalize(name)).collect();
+        Self { headers }
+    }
+
+    /// Whether every named column can be found
+    pub fn has_all(&self, names: &[&str]) -> bool {
+        names.iter().all(|name| self.index(name).is_ok())
+    }
+
+    /// Finds the column whose header is `name`, or failing that, the first column whose header starts
+    /// with `name`. Case and surrounding whitespace are ignored. An empty name only finds a column
+    /// with an empty header, such as one holding buttons or links.
+    pub fn index(&self, name: &str) -> Result<usize> {
+        let name = normalize(name);
+        let exact = self.headers.iter().position(|header| *header == name);
+        let prefix = || {
+            self.headers
+                .iter()
+                .position(|header| !name.is_empty() && header.starts_with(&name))
+        };
+
+        exact.or_else(prefix).with_context(|| {
+            format!(
+                "couldn't find column \"{name}\" in headers {:?}",
+                self.headers
+            )
+        })
+    }
+
+    /// Gets the cell in the named column from a row of cells
+    pub fn cell<'a>(&self, cells: &[ElementRef<'a>], name: &str) -> Result<ElementRef<'a>> {
+        let index = self.index(name)?;
+        cells
+            .get(index)
+            .copied()
+            .with_context(|| format!("missing \"{name}\" entry"))
+    }
+}
+
+fn normalize(header: &str) -> String {
+    header.trim().to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use scraper::{Html, Selector};
+
+    use super::*;
+
+    /// Parses a regrade-like table, returning its columns and the cells of its first body row
+    fn parse(table: &str) -> (TableColumns, Html) {
+        let html = Html::parse_fragment(table);
+        let header = Selector::parse("table > thead > tr > th").unwrap();
+        let columns = html.select(&header).collect();
+        (columns, html)
+    }
+
+    fn first_row_cells(html: &Html) -> Vec<ElementRef> {
+        let cell = Selector::parse("table > tbody > tr > td").unwrap();
+        html.select(&cell).collect()
+    }
+
+    fn cell_text(columns: &TableColumns, cells: &[ElementRef], name: &str) -> String {
+        text(columns.cell(cells, name).unwrap())
+    }
+
+    const REGRADES: &str = r#"
+        <table>
+            <thead><tr><th>Student</th><th>Question</th><th>Grader</th><th>Completed</th><th></th></tr></thead>
+            <tbody><tr><td>Ada</td><td>1: Proofs</td><td>Grace</td><td></td><td><a href="/r/1">Review</a></td></tr></tbody>
+        </table>
+    "#;
+
+    #[test]
+    fn finds_columns_by_header() {
+        let (columns, html) = parse(REGRADES);
+        let cells = first_row_cells(&html);
+
+        assert_eq!(cell_text(&columns, &cells, "student"), "Ada");
+        assert_eq!(cell_text(&columns, &cells, "Question"), "1: Proofs");
+        assert_eq!(cell_text(&columns, &cells, " grader "), "Grace");
+    }
+
+    #[test]
+    fn finds_reordered_columns() {
+        let (columns, html) = parse(
+            r#"
+            <table>
+                <thead><tr><th></th><th>Grader</th><th>Student</th><th>Question</th></tr></thead>
+                <tbody><tr><td><a href="/r/1">Review</a></td><td>Grace</td><td>Ada</td><td>1: Proofs</td></tr></tbody>
+            </table>
+            "#,
+        );
+        let cells = first_row_cells(&html);
+
+        assert_eq!(columns.index("").unwrap(), 0);
+        assert_eq!(cell_text(&columns, &cells, "student"), "Ada");
+        assert_eq!(cell_text(&columns, &cells, "grader"), "Grace");
+    }
+
+    #[test]
+    fn ignores_extra_columns() {
+        let (columns, html) = parse(
+            r#"
+            <table>
+                <thead><tr><th>Student</th><th>Sections</th><th>Question</th><th></th></tr></thead>
+                <tbody><tr><td>Ada</td><td>012</td><td>1: Proofs</td><td><a href="/r/1">Review</a></td></tr></tbody>
+            </table>
+            "#,
+        );
+        let cells = first_row_cells(&html);
+
+        assert_eq!(cell_text(&columns, &cells, "student"), "Ada");
+        assert_eq!(cell_text(&columns, &cells, "question"), "1: Proofs");
+        assert_eq!(columns.index("").unwrap(), 3);
+    }
+
+    #[test]
+    fn prefers_exact_header_over_prefix() {
+        let (columns, _) =
+            parse("<table><thead><tr><th>Student Email</th><th>Student</th></tr></thead></table>");
+
+        assert_eq!(columns.index("student").unwrap(), 1);
+        assert_eq!(columns.index("student email").unwrap(), 0);
+    }
+
+    #[test]
+    fn matches_header_prefix() {
+        let (columns, _) =
+            parse("<table><thead><tr><th>Student</th><th>Completed?</th></tr></thead></table>");
+
+        assert_eq!(columns.index("completed").unwrap(), 1);
+    }
+
+    #[test]
+    fn empty_name_only_matches_empty_header() {
+        let (columns, html) = parse(REGRADES);
+        let cells = first_row_cells(&html);
+        let link = columns.cell(&cells, "").unwrap();
+
+        assert_eq!(columns.index("").unwrap(), 4);
+        assert_eq!(text(link), "Review");
+
+        let (columns, _) =
+            parse("<table><thead><tr><th>Student</th><th>Question</th></tr></thead></table>");
+        assert!(columns.index("").is_err());
+    }
+
+    #[test]
+    fn missing_thead_finds_no_columns() {
+        let (columns, html) = parse(
+            r#"
+            <table>
+                <tbody><tr><td>Ada</td><td>1: Proofs</td><td>Grace</td></tr></tbody>
+            </table>
+            "#,
+        );
+        let cells = first_row_cells(&html);
+
+        assert!(columns.index("student").is_err());
+        assert!(columns.cell(&cells, "student").is_err());
+    }
+
+    #[test]
+    fn checks_for_every_column() {
+        let (columns, _) = parse(REGRADES);
+
+        assert!(columns.has_all(&["student", "question", "completed"]));
+        assert!(!columns.has_all(&["student", "sections"]));
+    }
+
+    #[test]
+    fn positional_columns_read_cells_in_order() {
+        let (_, html) = parse(
+            r#"
+            <table>
+                <tbody><tr><td>Ada</td><td>012</td><td>1: Proofs</td></tr></tbody>
+            </table>
+            "#,
+        );
+        let cells = first_row_cells(&html);
+        let columns = TableColumns::positional(&["Student", "Sections", "Question"]);
+
+        assert_eq!(cell_text(&columns, &cells, "student"), "Ada");
+        assert_eq!(cell_text(&columns, &cells, "question"), "1: Proofs");
+        assert!(columns.cell(&cells, "grader").is_err());
+    }
+
+    #[test]
+    fn missing_cell_is_an_error() {
+        let (columns, html) = parse(
+            r#"
+            <table>
+                <thead><tr><th>Student</th><th>Question</th></tr></thead>
+                <tbody><tr><td>Ada</td></tr></tbody>
+            </table>
+            "#,
+        );
+        let cells = first_row_cells(&html);
+
+        assert!(columns.cell(&cells, "question").is_err());
+    }
+}
@@ -1,27 +1,62 @@
 //! Holds types that don't "do" much (at least at present), especially when it would be difficult to
 //! place them before further building out the Gradescope data model.
 
+use std::cmp::Ordering;
+use std::convert::Infallible;
 use std::fmt;
 use std::num::FpCategory;
+use std::str::FromStr;
 
 use anyhow::{bail, Result};
-use serde::Serialize;
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
 
-// Not just an integer because of question parts. For example, part 2 of question 3 is "3.2".
-// TODO: parse as a sequence of integers
-#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
+// Not just an integer because of question parts. For example, part 2 of question 3 is "3.2", but
+// other courses may write it as "3(b)" or "3.ii".
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
 pub struct QuestionNumber {
     number: String,
+    parts: Option<Vec<QuestionPart>>,
 }
 
 impl QuestionNumber {
     pub fn new(number: String) -> Self {
-        Self { number }
+        let parts = QuestionPart::parse_all(&number);
+        Self { number, parts }
     }
 
     pub fn as_str(&self) -> &str {
         &self.number
     }
+
+    /// Each part of the number from outermost to innermost, such as 1, b, and ii for "1(b)(ii)", or
+    /// `None` if it isn't in a style we recognize
+    pub fn parts(&self) -> Option<&[QuestionPart]> {
+        self.parts.as_deref()
+    }
+
+    /// The number written in a consistent style, such as "1.b.ii" for "1(b)(ii)", falling back to the
+    /// original text
+    pub fn normalized(&self) -> String {
+        match &self.parts {
+            Some(parts) => parts.iter().join("."),
+            None => self.number.clone(),
+        }
+    }
+}
+
+// Numbers we can parse sort by their parts, so "10" comes after "9"
+impl Ord for QuestionNumber {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (&self.parts, &self.number).cmp(&(&other.parts, &other.number))
+    }
+}
+
+impl PartialOrd for QuestionNumber {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
 }
 
 impl fmt::Display for QuestionNumber {
@@ -30,7 +65,142 @@ impl fmt::Display for QuestionNumber {
     }
 }
 
-#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
+impl FromStr for QuestionNumber {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self::new(s.to_owned()))
+    }
+}
+
+impl From<String> for QuestionNumber {
+    fn from(number: String) -> Self {
+        Self::new(number)
+    }
+}
+
+impl From<QuestionNumber> for String {
+    fn from(number: QuestionNumber) -> Self {
+        number.number
+    }
+}
+
+/// One level of a question number, each holding its 1-based index
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum QuestionPart {
+    /// Such as the 3 in "3.2"
+    Number(u32),
+    /// Such as the b in "1(b)", where a is 1
+    Letter(u32),
+    /// Such as the ii in "1(b)(ii)"
+    Roman(u32),
+}
+
+impl QuestionPart {
+    pub fn index(self) -> u32 {
+        match self {
+            Self::Number(index) | Self::Letter(index) | Self::Roman(index) => index,
+        }
+    }
+
+    /// Splits a number into runs of digits and letters, separated by things like "." and "()" or by
+    /// switching between digits and letters, as in "1a". The first run must be a number. After
+    /// that, a run of letters is a roman numeral if it's one up to [`MAX_ROMAN`], as in "1.v" or
+    /// "1(a)(iii)". Otherwise, it must be a single letter.
+    fn parse_all(number: &str) -> Option<Vec<Self>> {
+        let runs = number
+            .split(|c: char| matches!(c, '.' | '(' | ')') || c.is_whitespace())
+            .flat_map(|token| {
+                token
+                    .chars()
+                    .group_by(char::is_ascii_digit)
+                    .into_iter()
+                    .map(|(is_digits, run)| (is_digits, run.collect::<String>()))
+                    .collect_vec()
+            });
+
+        let mut parts = Vec::new();
+        for (is_digits, run) in runs {
+            if parts.is_empty() && !is_digits {
+                return None;
+            }
+
+            let part = if is_digits {
+                Self::Number(run.parse().ok()?)
+            } else if let Some(index) = parse_roman(&run) {
+                Self::Roman(index)
+            } else {
+                Self::Letter(parse_letter(&run)?)
+            };
+            parts.push(part);
+        }
+
+        (!parts.is_empty()).then_some(parts)
+    }
+}
+
+impl fmt::Display for QuestionPart {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Self::Number(index) => index.fmt(f),
+            Self::Letter(index) => match char::from_u32('a' as u32 + index - 1) {
+                Some(letter) => letter.fmt(f),
+                None => index.fmt(f),
+            },
+            Self::Roman(index) => format_roman(index).fmt(f),
+        }
+    }
+}
+
+fn parse_letter(text: &str) -> Option<u32> {
+    let mut chars = text.chars();
+    match (chars.next(), chars.next()) {
+        (Some(letter), None) if letter.is_ascii_alphabetic() => {
+            Some(letter.to_ascii_lowercase() as u32 - 'a' as u32 + 1)
+        }
+        _ => None,
+    }
+}
+
+const ROMAN_NUMERALS: [(u32, &str); 5] = [(10, "x"), (9, "ix"), (5, "v"), (4, "iv"), (1, "i")];
+
+/// The largest roman numeral a question part can be, which is the largest written with only i, v,
+/// and x
+const MAX_ROMAN: u32 = 39;
+
+/// Parses a lowercase or uppercase roman numeral from i to [`MAX_ROMAN`], rejecting ones not
+/// written the standard way
+fn parse_roman(text: &str) -> Option<u32> {
+    let text = text.to_ascii_lowercase();
+
+    let mut rest = text.as_str();
+    let mut value = 0;
+
+    for (numeral_value, numeral) in ROMAN_NUMERALS {
+        while let Some(stripped) = rest.strip_prefix(numeral) {
+            value += numeral_value;
+            rest = stripped;
+        }
+    }
+
+    let standard =
+        rest.is_empty() && value > 0 && value <= MAX_ROMAN && format_roman(value) == text;
+    standard.then_some(value)
+}
+
+fn format_roman(mut value: u32) -> String {
+    let mut roman = String::new();
+    for (numeral_value, numeral) in ROMAN_NUMERALS {
+        while value >= numeral_value {
+            roman.push_str(numeral);
+            value -= numeral_value;
+        }
+    }
+    roman
+}
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(transparent)]
 pub struct QuestionTitle {
     title: String,
 }
@@ -51,7 +221,15 @@ impl fmt::Display for QuestionTitle {
     }
 }
 
-#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+impl FromStr for QuestionTitle {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self::new(s.to_owned()))
+    }
+}
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 #[serde(transparent)]
 pub struct GraderName {
     name: String,
@@ -73,7 +251,15 @@ impl fmt::Display for GraderName {
     }
 }
 
-#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+impl FromStr for GraderName {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self::new(s.to_owned()))
+    }
+}
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 #[serde(transparent)]
 pub struct StudentName {
     name: String,
@@ -95,7 +281,47 @@ impl fmt::Display for StudentName {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+impl FromStr for StudentName {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self::new(s.to_owned()))
+    }
+}
+
+/// A lecture or discussion section, such as "012"
+#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Section {
+    name: String,
+}
+
+impl Section {
+    pub fn new(name: String) -> Self {
+        Self { name }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.name
+    }
+}
+
+impl fmt::Display for Section {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.name.fmt(f)
+    }
+}
+
+impl FromStr for Section {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self::new(s.to_owned()))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[serde(try_from = "f32", into = "f32")]
 pub struct Points {
     points: f32,
 }
@@ -112,3 +338,122 @@ impl Points {
         self.points
     }
 }
+
+impl TryFrom<f32> for Points {
+    type Error = anyhow::Error;
+
+    fn try_from(points: f32) -> Result<Self> {
+        Self::new(points)
+    }
+}
+
+impl From<Points> for f32 {
+    fn from(points: Points) -> Self {
+        points.as_f32()
+    }
+}
+
+impl fmt::Display for Points {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.points.fmt(f)
+    }
+}
+
+impl FromStr for Points {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::new(s.trim().parse()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use QuestionPart::{Letter, Number, Roman};
+
+    fn parts(number: &str) -> Option<Vec<QuestionPart>> {
+        QuestionNumber::new(number.to_owned())
+            .parts()
+            .map(<[_]>::to_vec)
+    }
+
+    #[test]
+    fn parses_letter_parts() {
+        assert_eq!(parts("1(a)"), Some(vec![Number(1), Letter(1)]));
+        assert_eq!(parts("1.b"), Some(vec![Number(1), Letter(2)]));
+        assert_eq!(parts("1b"), Some(vec![Number(1), Letter(2)]));
+        assert_eq!(parts("3.2"), Some(vec![Number(3), Number(2)]));
+    }
+
+    #[test]
+    fn parses_roman_parts() {
+        assert_eq!(
+            parts("1(b)(ii)"),
+            Some(vec![Number(1), Letter(2), Roman(2)])
+        );
+        assert_eq!(parts("1(a)(i)"), Some(vec![Number(1), Letter(1), Roman(1)]));
+        assert_eq!(
+            QuestionNumber::new("1(b)(ii)".to_owned()).normalized(),
+            "1.b.ii"
+        );
+    }
+
+    #[test]
+    fn parses_roman_parts_after_numbers() {
+        let numerals = ["i", "ii", "iii", "iv", "v", "vi", "vii", "viii", "ix", "x"];
+        for (index, numeral) in (1..).zip(numerals) {
+            assert_eq!(
+                parts(&format!("1.{numeral}")),
+                Some(vec![Number(1), Roman(index)]),
+                "1.{numeral}"
+            );
+        }
+    }
+
+    #[test]
+    fn sorts_roman_parts() {
+        let mut numbers = ["1.x", "1.ix", "1.v", "1.ii", "1.i", "1.iv"]
+            .map(|number| QuestionNumber::new(number.to_owned()));
+        numbers.sort();
+        let numbers = numbers.iter().map(QuestionNumber::as_str).collect_vec();
+        assert_eq!(numbers, ["1.i", "1.ii", "1.iv", "1.v", "1.ix", "1.x"]);
+    }
+
+    #[test]
+    fn sorts_10_after_9() {
+        let mut numbers =
+            ["10", "9", "1.10", "1.9", "2"].map(|number| QuestionNumber::new(number.to_owned()));
+        numbers.sort();
+        let numbers = numbers.iter().map(QuestionNumber::as_str).collect_vec();
+        assert_eq!(numbers, ["1.9", "1.10", "2", "9", "10"]);
+    }
+
+    #[test]
+    fn only_reads_small_roman_numerals() {
+        assert_eq!(
+            parts("1(b)(c)"),
+            Some(vec![Number(1), Letter(2), Letter(3)])
+        );
+        assert_eq!(parts("1.c"), Some(vec![Number(1), Letter(3)]));
+        assert_eq!(parts("1.xxxix"), Some(vec![Number(1), Roman(39)]));
+        assert_eq!(parts("1.xl"), None);
+        assert_eq!(parts("1.cd"), None);
+        assert_eq!(parts("1.mix"), None);
+        assert_eq!(parts("1.iiii"), None);
+    }
+
+    #[test]
+    fn must_start_with_a_number() {
+        assert_eq!(parts("Q1"), None);
+        assert_eq!(parts("a.1"), None);
+        assert_eq!(QuestionNumber::new("Q1".to_owned()).normalized(), "Q1");
+    }
+
+    #[test]
+    fn keeps_unrecognized_numbers() {
+        assert_eq!(parts("1.ab"), None);
+        assert_eq!(QuestionNumber::new("1.ab".to_owned()).normalized(), "1.ab");
+    }
+}
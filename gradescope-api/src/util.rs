@@ -1,4 +1,6 @@
+use anyhow::{Context, Result};
 use scraper::ElementRef;
+use url::Url;
 
 use crate::assignment::Assignment;
 use crate::course::Course;
@@ -9,6 +11,53 @@ pub const ACCOUNT_PATH: &str = "/account";
 pub const ASSIGNMENTS_COURSE_PATH: &str = "/assignments";
 pub const REGRADES_ASSIGNMENT_PATH: &str = "/regrade_requests";
 
+/// Defines a string-backed id type that parses from either a bare id or a Gradescope URL with the
+/// id following `$collection`
+macro_rules! string_id {
+    ($(#[$attr:meta])* $name:ident, $collection:literal) => {
+        $(#[$attr])*
+        #[derive(
+            Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, serde::Serialize, serde::Deserialize,
+        )]
+        #[serde(transparent)]
+        pub struct $name {
+            id: String,
+        }
+
+        impl $name {
+            pub fn new(id: String) -> Self {
+                Self { id }
+            }
+
+            pub fn as_str(&self) -> &str {
+                &self.id
+            }
+        }
+
+        impl AsRef<str> for $name {
+            fn as_ref(&self) -> &str {
+                &self.id
+            }
+        }
+
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                self.id.fmt(f)
+            }
+        }
+
+        impl std::str::FromStr for $name {
+            type Err = anyhow::Error;
+
+            fn from_str(s: &str) -> anyhow::Result<Self> {
+                $crate::util::parse_id(s, $collection).map(Self::new)
+            }
+        }
+    };
+}
+
+pub(crate) use string_id;
+
 pub fn gs_url(path: &str) -> String {
     format!("{BASE_URL}{path}")
 }
@@ -25,6 +74,32 @@ pub fn text(el: ElementRef) -> String {
     el.text().flat_map(|text| text.chars()).collect()
 }
 
+/// Parses an id given either bare, such as `123456`, or as the path segment following `collection`
+/// in a Gradescope URL or path, such as `https://www.gradescope.com/courses/123456/assignments`
+pub fn parse_id(text: &str, collection: &str) -> Result<String> {
+    let text = text.trim();
+    if is_id(text) {
+        return Ok(text.to_owned());
+    }
+
+    let url = Url::parse(text)
+        .or_else(|_| Url::parse(BASE_URL).and_then(|base| base.join(text)))
+        .with_context(|| format!("\"{text}\" is neither an id nor a URL"))?;
+    let mut segments = url
+        .path_segments()
+        .with_context(|| format!("URL \"{text}\" has no path"))?;
+    segments
+        .find(|segment| *segment == collection)
+        .and_then(|_| segments.next())
+        .filter(|id| is_id(id))
+        .map(ToOwned::to_owned)
+        .with_context(|| format!("couldn't find an id following `{collection}` in \"{text}\""))
+}
+
+fn is_id(text: &str) -> bool {
+    !text.is_empty() && text.chars().all(|c| c.is_ascii_digit())
+}
+
 pub fn id_from_link(link: ElementRef) -> Option<String> {
     link.value()
         .attr("href")?
@@ -32,3 +107,43 @@ pub fn id_from_link(link: ElementRef) -> Option<String> {
         .last()
         .map(ToOwned::to_owned)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_bare_id() {
+        assert_eq!(parse_id("123456", "courses").unwrap(), "123456");
+        assert_eq!(parse_id("  123456\n", "courses").unwrap(), "123456");
+    }
+
+    #[test]
+    fn parses_full_url() {
+        let url = "https://www.gradescope.com/courses/123456/assignments/789/submissions/42";
+        assert_eq!(parse_id(url, "courses").unwrap(), "123456");
+        assert_eq!(parse_id(url, "assignments").unwrap(), "789");
+    }
+
+    #[test]
+    fn parses_relative_path() {
+        let path = "/courses/123456/assignments/789/regrade_requests";
+        assert_eq!(parse_id(path, "assignments").unwrap(), "789");
+        assert_eq!(parse_id("courses/123456", "courses").unwrap(), "123456");
+    }
+
+    #[test]
+    fn parses_url_without_scheme() {
+        let url = "www.gradescope.com/courses/123456/assignments/789";
+        assert_eq!(parse_id(url, "courses").unwrap(), "123456");
+        assert_eq!(parse_id(url, "assignments").unwrap(), "789");
+    }
+
+    #[test]
+    fn rejects_missing_or_non_numeric_id() {
+        assert!(parse_id("https://www.gradescope.com/courses/123456", "assignments").is_err());
+        assert!(parse_id("/courses/abc/assignments", "courses").is_err());
+        assert!(parse_id("/courses", "courses").is_err());
+        assert!(parse_id("", "courses").is_err());
+    }
+}
@@ -0,0 +1,43 @@
+-- Courses without a short name, such as workshops, store NULL instead of an empty string. SQLite
+-- can't drop NOT NULL in place, so the tables are rebuilt, children first so that foreign keys stay
+-- satisfied throughout.
+ALTER TABLE regrade RENAME TO regrade_old;
+ALTER TABLE assignment RENAME TO assignment_old;
+ALTER TABLE instructor_course RENAME TO instructor_course_old;
+
+CREATE TABLE instructor_course(
+    id TEXT PRIMARY KEY NOT NULL, -- Gradescope ID
+    short_name TEXT,
+    name TEXT NOT NULL
+);
+
+CREATE TABLE assignment(
+    id TEXT PRIMARY KEY NOT NULL,
+    course_id TEXT NOT NULL,
+    name TEXT NOT NULL,
+    points REAL NOT NULL,
+    FOREIGN KEY (course_id) REFERENCES instructor_course(id)
+);
+
+CREATE TABLE regrade(
+    assignment_id TEXT NOT NULL,
+    student_name TEXT NOT NULL,
+    question_number TEXT NOT NULL,
+    question_title TEXT NOT NULL,
+    grader_name TEXT NOT NULL,
+    completed TINYINT NOT NULL DEFAULT false,
+    FOREIGN KEY (assignment_id) REFERENCES assignment(id)
+);
+
+INSERT INTO instructor_course (id, short_name, name)
+SELECT id, NULLIF(short_name, ''), name FROM instructor_course_old;
+
+INSERT INTO assignment (id, course_id, name, points)
+SELECT id, course_id, name, points FROM assignment_old;
+
+INSERT INTO regrade (assignment_id, student_name, question_number, question_title, grader_name, completed)
+SELECT assignment_id, student_name, question_number, question_title, grader_name, completed FROM regrade_old;
+
+DROP TABLE regrade_old;
+DROP TABLE assignment_old;
+DROP TABLE instructor_course_old;
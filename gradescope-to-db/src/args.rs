@@ -0,0 +1,118 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Result};
+use clap::{Parser, Subcommand};
+use gradescope_api::prelude::*;
+
+/// The crate version, plus the commit and time (in seconds since the Unix epoch) it was built from
+pub const VERSION: &str = concat!(
+    env!("CARGO_PKG_VERSION"),
+    " (",
+    env!("GIT_SHA"),
+    ", built at ",
+    env!("BUILD_TIME"),
+    ")"
+);
+
+/// Scrape course data into a database
+#[derive(Debug, Parser)]
+#[command(version = VERSION)]
+pub struct Args {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// Only sync these assignments, each given by name, id, or Gradescope URL
+    #[arg(long, num_args = 1.., conflicts_with = "full")]
+    assignments: Vec<String>,
+
+    /// Don't sync regrade requests
+    #[arg(long, conflicts_with = "full")]
+    skip_regrades: bool,
+
+    /// Sync every assignment and its regrade requests, which is the default
+    #[arg(long)]
+    full: bool,
+
+    /// Write the JSON record of the run to this file instead of to `cache/runs`
+    #[arg(long, global = true)]
+    manifest: Option<PathBuf>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Check the environment, Gradescope login, course, and database without syncing anything
+    Check,
+}
+
+impl Args {
+    /// Where to write the record of the run, if not the default
+    pub fn manifest(&self) -> Option<&Path> {
+        self.manifest.as_deref()
+    }
+
+    pub fn scope(&self) -> SyncScope {
+        if self.full {
+            return SyncScope::default();
+        }
+
+        SyncScope {
+            assignments: self.assignments.clone(),
+            regrades: !self.skip_regrades,
+        }
+    }
+}
+
+/// Which parts of the course to sync
+#[derive(Debug, Clone)]
+pub struct SyncScope {
+    /// Assignment selectors, where empty means every assignment
+    assignments: Vec<String>,
+    regrades: bool,
+}
+
+impl Default for SyncScope {
+    fn default() -> Self {
+        Self {
+            assignments: Vec::new(),
+            regrades: true,
+        }
+    }
+}
+
+impl SyncScope {
+    pub fn regrades(&self) -> bool {
+        self.regrades
+    }
+
+    /// Picks out the assignments to sync, failing if any selector matches nothing
+    pub fn select_assignments(&self, assignments: Vec<Assignment>) -> Result<Vec<Assignment>> {
+        if self.assignments.is_empty() {
+            return Ok(assignments);
+        }
+
+        for selector in &self.assignments {
+            if !assignments
+                .iter()
+                .any(|assignment| matches(selector, assignment))
+            {
+                bail!("no assignment matches \"{selector}\"");
+            }
+        }
+
+        Ok(assignments
+            .into_iter()
+            .filter(|assignment| {
+                self.assignments
+                    .iter()
+                    .any(|selector| matches(selector, assignment))
+            })
+            .collect())
+    }
+}
+
+fn matches(selector: &str, assignment: &Assignment) -> bool {
+    assignment.name().as_str() == selector
+        || selector
+            .parse::<AssignmentId>()
+            .is_ok_and(|id| id == *assignment.id())
+}
@@ -0,0 +1,59 @@
+//! Checks that the environment is set up correctly, without syncing anything.
+
+use anyhow::{bail, Result};
+use gradescope_api::prelude::*;
+use sqlx::SqlitePool;
+
+use crate::env::Config;
+
+pub async fn check_setup() -> Result<()> {
+    let mut checks = Checks::default();
+
+    if let Some(config) = checks.report("read configuration", Config::from_env()) {
+        let Config {
+            creds,
+            course_name,
+            database_url,
+        } = config;
+
+        let login = async { Client::new(creds).await?.login().await };
+        if let Some(gradescope) = checks.report("log in to Gradescope", login.await) {
+            let course = async {
+                let (instructor_courses, _student_courses) = gradescope.get_courses().await?;
+                Course::find(&course_name, instructor_courses)
+            };
+            checks.report("find course", course.await);
+        }
+
+        let db = async { Ok(SqlitePool::connect(&database_url).await?) };
+        checks.report("connect to database", db.await);
+    }
+
+    if checks.failed > 0 {
+        bail!("{} check(s) failed", checks.failed);
+    }
+
+    println!("all checks passed");
+    Ok(())
+}
+
+#[derive(Debug, Default)]
+struct Checks {
+    failed: usize,
+}
+
+impl Checks {
+    fn report<T>(&mut self, name: &str, result: Result<T>) -> Option<T> {
+        match result {
+            Ok(value) => {
+                println!("[ok]   {name}");
+                Some(value)
+            }
+            Err(err) => {
+                println!("[fail] {name}: {err:#}");
+                self.failed += 1;
+                None
+            }
+        }
+    }
+}
@@ -1,24 +1,26 @@
 use std::env;
 
-use anyhow::Result;
+use anyhow::{bail, Context, Result};
 use dotenvy::dotenv;
-use gradescope_api::client::{Auth, Client};
-use gradescope_api::course::Course;
+use gradescope_api::prelude::*;
 
 pub async fn init_from_env() -> Result<InitFromEnv> {
-    dotenv().unwrap();
-
-    let course_name = course_name_from_env();
+    let Config {
+        creds,
+        course_name,
+        database_url,
+    } = Config::from_env()?;
 
-    let gradescope = Client::from_env().await?.login().await?;
+    let gradescope = Client::new(creds).await?.login().await?;
 
     let (instructor_courses, _student_courses) = gradescope.get_courses().await?;
-    let course = Course::find_by_short_name(&course_name, instructor_courses)?;
+    let course = Course::find(&course_name, instructor_courses)?;
 
     Ok(InitFromEnv {
         course,
         gradescope,
         course_name,
+        database_url,
     })
 }
 
@@ -26,12 +28,50 @@ pub struct InitFromEnv {
     pub course: Course,
     pub gradescope: Client<Auth>,
     pub course_name: String,
+    pub database_url: String,
 }
 
-pub fn db_url_from_env() -> String {
-    env::var("DATABASE_URL").unwrap()
+/// Settings read from the environment, including `.env` if present
+#[derive(Debug)]
+pub struct Config {
+    pub creds: Creds,
+    pub course_name: String,
+    pub database_url: String,
+}
+
+impl Config {
+    /// Reads the config, reporting every missing variable at once rather than just the first
+    pub fn from_env() -> Result<Self> {
+        match dotenv() {
+            Ok(_) => {}
+            Err(err) if err.not_found() => {}
+            Err(err) => return Err(err).context("couldn't load `.env`"),
+        }
+
+        let mut missing = Vec::new();
+        let email = var("EMAIL", &mut missing);
+        let password = var("GS_PASSWORD", &mut missing);
+        let course_name = var("COURSE_NAME", &mut missing);
+        let database_url = var("DATABASE_URL", &mut missing);
+
+        match (email, password, course_name, database_url) {
+            (Some(email), Some(password), Some(course_name), Some(database_url)) => Ok(Self {
+                creds: Creds::new(email, password),
+                course_name,
+                database_url,
+            }),
+            _ => bail!(
+                "missing or invalid environment variable(s): {} (see `example.env`)",
+                missing.join(", ")
+            ),
+        }
+    }
 }
 
-fn course_name_from_env() -> String {
-    env::var("COURSE_NAME").unwrap()
+fn var(name: &'static str, missing: &mut Vec<&'static str>) -> Option<String> {
+    let value = env::var(name).ok();
+    if value.is_none() {
+        missing.push(name);
+    }
+    value
 }
@@ -1,61 +1,122 @@
+use std::path::Path;
+
 use anyhow::Result;
-use env::{db_url_from_env, init_from_env, InitFromEnv};
-use gradescope_api::assignment::Assignment;
-use gradescope_api::client::{Auth, Client as GsConnection};
-use gradescope_api::course::Course;
-use gradescope_api::regrade::Regrade;
+use args::{Args, Command, SyncScope};
+use check::check_setup;
+use clap::Parser;
+use env::{init_from_env, InitFromEnv};
+use gradescope_api::prelude::*;
+use manifest::RunManifest;
 use sqlx::SqlitePool;
 
+mod args;
+mod check;
 mod env;
+mod manifest;
 
 #[tokio::main]
 async fn main() -> Result<()> {
+    let args = Args::parse();
+
+    let mut manifest = RunManifest::start();
+    let result = match args.command {
+        Some(Command::Check) => check_setup().await,
+        None => sync(&args.scope(), &mut manifest).await,
+    };
+
+    manifest.finish(&result);
+    let path = args
+        .manifest()
+        .map_or_else(|| manifest.default_path(), Path::to_path_buf);
+    // a failure to save shouldn't hide why the run itself failed
+    let saved = manifest.save(&path);
+    if saved.is_ok() {
+        println!("wrote run manifest to {}", path.display());
+    }
+    result.and(saved)
+}
+
+async fn sync(scope: &SyncScope, manifest: &mut RunManifest) -> Result<()> {
     let InitFromEnv {
         course,
         gradescope,
         course_name: _,
+        database_url,
     } = init_from_env().await?;
 
-    let db_pool = SqlitePool::connect(&db_url_from_env()).await?;
+    let db_pool = SqlitePool::connect(&database_url).await?;
 
-    add_course(&db_pool, &gradescope, &course).await?;
+    manifest.set_course(&course);
+    let result = add_course(&db_pool, &gradescope, &course, scope, manifest).await;
 
-    Ok(())
+    // report even if the run failed, since a failed run still made requests
+    let request_stats = gradescope.request_stats();
+    manifest.set_requests(&request_stats);
+    println!("{request_stats}");
+
+    let selector_stats = SelectorStats::snapshot();
+    if selector_stats.has_misses() {
+        println!("some pages were missing expected elements:\n{selector_stats}");
+    }
+
+    let warnings = gradescope.take_warnings();
+    if !warnings.is_empty() {
+        println!(
+            "{} thing(s) were skipped or only partially parsed:",
+            warnings.len()
+        );
+        for warning in &warnings {
+            println!("{warning}");
+        }
+    }
+
+    result
 }
 
 async fn add_course(
     db_pool: &SqlitePool,
-    gradescope: &GsConnection<Auth>,
+    gradescope: &Client<Auth>,
     course: &Course,
+    scope: &SyncScope,
+    manifest: &mut RunManifest,
 ) -> Result<()> {
     insert_course(db_pool, course).await?;
 
     let assignments = gradescope.get_assignments(course).await?;
+    let assignments = scope.select_assignments(assignments)?;
     for assignment in &assignments {
-        add_assignment(db_pool, gradescope, course, assignment).await?;
+        let regrades = add_assignment(db_pool, gradescope, course, assignment, scope).await?;
+        manifest.add_assignment(assignment, regrades);
     }
 
     Ok(())
 }
+
+/// Returns how many regrades were synced, if any were
 async fn add_assignment(
     db_pool: &SqlitePool,
-    gradescope: &GsConnection<Auth>,
+    gradescope: &Client<Auth>,
     course: &Course,
     assignment: &Assignment,
-) -> Result<()> {
+    scope: &SyncScope,
+) -> Result<Option<usize>> {
     insert_assignment(db_pool, course, assignment).await?;
 
+    if !scope.regrades() {
+        return Ok(None);
+    }
+
     let regrades = gradescope.get_regrades(course, assignment).await?;
     for regrade in &regrades {
         insert_regrade(db_pool, assignment, regrade).await?;
     }
 
-    Ok(())
+    Ok(Some(regrades.len()))
 }
 
 async fn insert_course(db_pool: &SqlitePool, course: &Course) -> Result<()> {
     let mut db = db_pool.acquire().await?;
-    let (id, short_name, name) = (course.id(), course.short_name(), course.name());
+    let (id, short_name, name) = (course.id().as_str(), course.short_name(), course.name());
 
     sqlx::query!(
         "
@@ -79,8 +140,8 @@ async fn insert_assignment(
 ) -> Result<()> {
     let mut db = db_pool.acquire().await?;
     let (id, course_id, name, points) = (
-        assignment.id(),
-        course.id(),
+        assignment.id().as_str(),
+        course.id().as_str(),
         assignment.name().as_str(),
         assignment.points().as_f32(),
     );
@@ -108,7 +169,7 @@ async fn insert_regrade(
 ) -> Result<()> {
     let mut db = db_pool.acquire().await?;
     let (assignment_id, student_name, question_number, question_title, grader_name, completed) = (
-        assignment.id(),
+        assignment.id().as_str(),
         regrade.student_name().as_str(),
         regrade.question_number().as_str(),
         regrade.question_title().as_str(),
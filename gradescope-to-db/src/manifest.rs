@@ -0,0 +1,99 @@
+//! A machine-readable record of a run, so that it's possible to tell afterwards exactly what was
+//! synced, when, and by which build.
+
+use std::fs::{self, File};
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, Result};
+use gradescope_api::prelude::*;
+use serde::Serialize;
+
+use crate::args::VERSION;
+
+/// Where manifests are written unless another path is given, relative to the working directory
+const MANIFEST_DIR: &str = "cache/runs";
+
+#[derive(Debug, Serialize)]
+pub struct RunManifest {
+    version: &'static str,
+    args: Vec<String>,
+    /// In seconds since the Unix epoch
+    started_at: u64,
+    finished_at: Option<u64>,
+    course: Option<CourseId>,
+    assignments: Vec<AssignmentManifest>,
+    requests: u64,
+    /// Why the run failed, if it did
+    error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct AssignmentManifest {
+    id: AssignmentId,
+    name: AssignmentName,
+    /// `None` if regrades weren't synced
+    regrades: Option<usize>,
+}
+
+impl RunManifest {
+    pub fn start() -> Self {
+        Self {
+            version: VERSION,
+            args: std::env::args().collect(),
+            started_at: now(),
+            finished_at: None,
+            course: None,
+            assignments: Vec::new(),
+            requests: 0,
+            error: None,
+        }
+    }
+
+    pub fn set_course(&mut self, course: &Course) {
+        self.course = Some(course.id().clone());
+    }
+
+    pub fn add_assignment(&mut self, assignment: &Assignment, regrades: Option<usize>) {
+        self.assignments.push(AssignmentManifest {
+            id: assignment.id().clone(),
+            name: assignment.name().clone(),
+            regrades,
+        });
+    }
+
+    pub fn set_requests(&mut self, stats: &RequestStats) {
+        self.requests = stats.total().requests();
+    }
+
+    pub fn finish(&mut self, result: &Result<()>) {
+        self.finished_at = Some(now());
+        self.error = result.as_ref().err().map(|err| format!("{err:#}"));
+    }
+
+    /// A path in [`MANIFEST_DIR`] named after when and by which process the run started, so that
+    /// runs don't overwrite each other's manifests
+    pub fn default_path(&self) -> PathBuf {
+        let name = format!("{}-{}.json", self.started_at, std::process::id());
+        Path::new(MANIFEST_DIR).join(name)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            fs::create_dir_all(dir)
+                .with_context(|| format!("could not create directory {}", dir.display()))?;
+        }
+        let file = File::create(path)
+            .with_context(|| format!("could not create manifest {}", path.display()))?;
+        serde_json::to_writer_pretty(BufWriter::new(file), self)
+            .with_context(|| format!("could not write manifest {}", path.display()))
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs())
+}
@@ -0,0 +1,109 @@
+//! A weekly summary for course staff, combining homework pairs and regrade stats into one Markdown
+//! document.
+
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::fmt;
+
+use gradescope_api::assignment::Assignment;
+use gradescope_api::regrade::Regrade;
+use itertools::Itertools;
+
+use crate::homework::pair::{HwPair, RegradesPair};
+use crate::homework::HwNumber;
+
+#[derive(Debug, Clone, Copy)]
+pub struct WeeklyDigest<'a> {
+    homeworks: &'a HashMap<HwNumber<'a>, HwPair<'a>>,
+    regrades: &'a HashMap<HwNumber<'a>, RegradesPair>,
+}
+
+impl<'a> WeeklyDigest<'a> {
+    pub fn new(
+        homeworks: &'a HashMap<HwNumber<'a>, HwPair<'a>>,
+        regrades: &'a HashMap<HwNumber<'a>, RegradesPair>,
+    ) -> Self {
+        Self {
+            homeworks,
+            regrades,
+        }
+    }
+
+    fn write_homeworks(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Numeric homework numbers sort numerically, so "10" comes after "9"
+        let homeworks = self
+            .homeworks
+            .iter()
+            .sorted_by_key(|(number, _)| (number.as_str().parse::<u32>().ok(), **number));
+
+        for (number, pair) in homeworks {
+            let regrades = self.regrades.get(number);
+
+            writeln!(f, "\n## Homework {number}\n")?;
+            writeln!(f, "| | Assignment | Points | Regrades | Pending |")?;
+            writeln!(f, "|---|---|---|---|---|")?;
+            if let Some(id) = pair.id() {
+                Self::write_row(f, "Individual", id, regrades.and_then(|r| r.id()))?;
+            }
+            if let Some(gw) = pair.gw() {
+                Self::write_row(f, "Groupwork", gw, regrades.and_then(|r| r.gw()))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn write_row(
+        f: &mut fmt::Formatter,
+        kind: &str,
+        assignment: &Assignment,
+        regrades: Option<&Vec<Regrade>>,
+    ) -> fmt::Result {
+        let regrades = regrades.map(Vec::as_slice).unwrap_or_default();
+        let pending = regrades
+            .iter()
+            .filter(|regrade| !regrade.completed())
+            .count();
+
+        writeln!(
+            f,
+            "| {kind} | {} | {} | {} | {pending} |",
+            assignment.name(),
+            assignment.points().as_f32(),
+            regrades.len(),
+        )
+    }
+
+    fn write_pending_by_grader(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let pending = self
+            .regrades
+            .values()
+            .flat_map(|pair| pair.as_ref().into_iter().flatten())
+            .filter(|regrade| !regrade.completed())
+            .counts_by(Regrade::grader_name);
+
+        writeln!(f, "\n## Pending regrades by grader\n")?;
+        if pending.is_empty() {
+            return writeln!(f, "No pending regrades.");
+        }
+
+        writeln!(f, "| Grader | Pending |")?;
+        writeln!(f, "|---|---|")?;
+        let most_pending_first = pending
+            .into_iter()
+            .sorted_by_key(|&(grader, count)| (Reverse(count), grader));
+        for (grader, count) in most_pending_first {
+            writeln!(f, "| {grader} | {count} |")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a> fmt::Display for WeeklyDigest<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "# Weekly digest")?;
+        self.write_homeworks(f)?;
+        self.write_pending_by_grader(f)
+    }
+}
@@ -2,7 +2,6 @@ use std::collections::HashMap;
 use std::ops::Deref;
 
 use anyhow::Result;
-use futures::{stream, StreamExt, TryStreamExt};
 use gradescope_api::assignment::Assignment;
 use gradescope_api::client::{Auth, Client};
 use gradescope_api::course::Course;
@@ -14,26 +13,26 @@ pub fn find_exams(assignments: &[Assignment]) -> HashMap<&str, Vec<Exam>> {
     exams.into_group_map_by(|exam| exam.number())
 }
 
+/// Fetches the regrades for each exam, several exams at once
 pub async fn get_exam_regrades<'a>(
     exams: &HashMap<&'a str, Vec<Exam<'a>>>,
     gradescope: &Client<Auth>,
     course: &Course,
 ) -> Result<HashMap<&'a str, Vec<(Exam<'a>, Vec<Regrade>)>>> {
-    let get_regrades = |assignment| gradescope.get_regrades(course, assignment);
-
-    stream::iter(exams)
-        .then(|(num, exams)| async move {
-            let regrades = stream::iter(exams).map(Deref::deref).then(get_regrades);
-            stream::iter(exams)
-                .map(Clone::clone)
-                .zip(regrades)
-                .map(|(x, y)| y.map(|y| (x, y)))
-                .try_collect::<Vec<_>>()
-                .await
-                .map(|x| (*num, x))
-        })
-        .try_collect()
-        .await
+    let all_exams = exams
+        .iter()
+        .flat_map(|(num, exams)| exams.iter().map(move |exam| (*num, *exam)))
+        .collect_vec();
+
+    let regrades = gradescope
+        .get_regrades_for(course, all_exams.iter().map(|(_, exam)| exam.assignment))
+        .await?;
+
+    Ok(all_exams
+        .into_iter()
+        .zip(regrades)
+        .map(|((num, exam), regrades)| (num, (exam, regrades)))
+        .into_group_map())
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -6,7 +6,8 @@
 //! homeworks may not have graded groupworks.
 //!
 //! The homework number is expected to be a nonnegative integer, but is represented as a string in
-//! case this changes.
+//! case this changes. A homework may also be a variant with a suffix after the number, such as
+//! "Homework 7 (Makeup)", which is paired separately from the plain homework with that number.
 //!
 //! # Terminology:
 //! - Homework/HW: an assignment that is a homework, including both individual and groupwork
@@ -19,14 +20,15 @@ use std::collections::HashMap;
 use std::iter::FilterMap;
 use std::ops::Deref;
 
-use anyhow::Result;
-use futures::{stream, StreamExt, TryStreamExt};
+use anyhow::{Context, Result};
 use gradescope_api::assignment::Assignment;
 use gradescope_api::client::{Auth, Client};
 use gradescope_api::course::Course;
 use gradescope_api::regrade::Regrade;
-use gradescope_api::types::{GraderName, StudentName};
-use serde::Serialize;
+use gradescope_api::snapshot::CourseSnapshot;
+use gradescope_api::types::{GraderName, Section, StudentName};
+use itertools::Itertools;
+use serde::{Serialize, Serializer};
 
 use self::pair::{HwPair, RegradeRefsPair, RegradesPair};
 
@@ -40,28 +42,78 @@ pub mod pair;
 /// ```text
 /// [(1, ID1+GW1), (2, GW2), (3, ID3), (4, ID4+GW4)]
 /// ```
-pub fn find_homeworks(assignments: &[Assignment]) -> HashMap<HwNumber, HwPair> {
-    let ids = Individual::get_from(assignments);
-    let gws = Groupwork::get_from(assignments);
+pub fn find_homeworks(assignments: &[Assignment], variants: Variants) -> HashMap<HwNumber, HwPair> {
+    let included = |number: HwNumber| variants == Variants::Include || !number.is_variant();
+    let ids = Individual::get_from(assignments).filter(|id| included(id.number()));
+    let gws = Groupwork::get_from(assignments).filter(|gw| included(gw.number()));
     HwPair::make_pairs(ids, gws)
 }
 
+/// Whether to include variants of homeworks, such as makeups
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Variants {
+    #[default]
+    Include,
+    Exclude,
+}
+
+/// Fetches the regrades for each homework, several homeworks at once
 pub async fn get_homework_regrades<'a>(
     homeworks: &HashMap<HwNumber<'a>, HwPair<'_>>,
     gradescope: &Client<Auth>,
     course: &Course,
 ) -> Result<HashMap<HwNumber<'a>, RegradesPair>> {
-    stream::iter(homeworks)
-        .then(|(num, pair)| async move {
+    let assignments = homeworks
+        .values()
+        .flat_map(|pair| pair.as_deref())
+        .cloned()
+        .collect_vec();
+    let regrades = gradescope.get_regrades_for(course, &assignments).await?;
+
+    let snapshot = CourseSnapshot::new(course.clone(), assignments.into_iter().zip(regrades));
+    snapshot_homework_regrades(homeworks, &snapshot)
+}
+
+/// Like [`get_homework_regrades`], but reads regrades from a snapshot instead of fetching them
+pub fn snapshot_homework_regrades<'a>(
+    homeworks: &HashMap<HwNumber<'a>, HwPair<'_>>,
+    snapshot: &CourseSnapshot,
+) -> Result<HashMap<HwNumber<'a>, RegradesPair>> {
+    homeworks
+        .iter()
+        .map(|(num, pair)| {
             pair.as_deref()
-                .map_same(|assignment| gradescope.get_regrades(course, assignment))
-                .join_both()
-                .await
+                .map_same(|assignment| {
+                    snapshot
+                        .regrades(assignment.id())
+                        .map(<[Regrade]>::to_vec)
+                        .with_context(|| {
+                            format!("snapshot is missing assignment {}", assignment.name())
+                        })
+                })
                 .try_both()
                 .map(|x| (*num, x))
         })
-        .try_collect()
-        .await
+        .collect()
+}
+
+/// Keeps only the regrades from students in a section
+pub fn filter_regrades_by_section<'num>(
+    regrades: HashMap<HwNumber<'num>, RegradesPair>,
+    section: &Section,
+) -> HashMap<HwNumber<'num>, RegradesPair> {
+    regrades
+        .into_iter()
+        .map(|(num, pair)| {
+            let pair = pair.map_same(|regrades| {
+                regrades
+                    .into_iter()
+                    .filter(|regrade| regrade.in_section(section))
+                    .collect()
+            });
+            (num, pair)
+        })
+        .collect()
 }
 
 pub fn group_regrades_by_grader<'map, 'num>(
@@ -91,25 +143,70 @@ pub trait HasHwNumber<'a> {
     fn number(&self) -> HwNumber<'a>;
 }
 
-#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize)]
-#[serde(transparent)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
 pub struct HwNumber<'a> {
     number: &'a str,
+    variant: Option<&'a str>,
 }
 
 impl<'a> HwNumber<'a> {
     pub fn new(number: &'a str) -> Self {
-        Self { number }
+        Self {
+            number,
+            variant: None,
+        }
+    }
+
+    pub fn with_variant(number: &'a str, variant: &'a str) -> Self {
+        Self {
+            number,
+            variant: Some(variant),
+        }
+    }
+
+    /// Parses what follows "Homework " or "Groupwork " in an assignment name, such as "7",
+    /// "7 (Makeup)", or "7-Late". The number is the leading digits, and anything after them is the
+    /// variant. Text that doesn't start with a digit is taken as the number.
+    fn parse(text: &'a str) -> Self {
+        let (number, suffix) = match text.find(|c: char| !c.is_ascii_digit()) {
+            Some(0) | None => (text, ""),
+            Some(index) => text.split_at(index),
+        };
+        let variant = suffix.trim_matches(|c: char| c.is_whitespace() || "()-".contains(c));
+
+        if variant.is_empty() {
+            Self::new(number)
+        } else {
+            Self::with_variant(number, variant)
+        }
     }
 
+    /// The number alone, without any variant
     pub fn as_str(self) -> &'a str {
         self.number
     }
+
+    pub fn variant(self) -> Option<&'a str> {
+        self.variant
+    }
+
+    pub fn is_variant(self) -> bool {
+        self.variant.is_some()
+    }
 }
 
 impl<'a> fmt::Display for HwNumber<'a> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        self.number.fmt(f)
+        match self.variant {
+            Some(variant) => write!(f, "{} ({variant})", self.number),
+            None => self.number.fmt(f),
+        }
+    }
+}
+
+impl<'a> Serialize for HwNumber<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
     }
 }
 
@@ -163,7 +260,7 @@ impl<'a> TryFrom<&'a Assignment> for Individual<'a> {
             .as_str()
             .strip_prefix("Homework ")
             .ok_or(())?;
-        let number = HwNumber::new(number_text);
+        let number = HwNumber::parse(number_text);
         Ok(Self { number, assignment })
     }
 }
@@ -203,7 +300,7 @@ impl<'a> TryFrom<&'a Assignment> for Groupwork<'a> {
             .as_str()
             .strip_prefix("Groupwork ")
             .ok_or(())?;
-        let number = HwNumber::new(number_text);
+        let number = HwNumber::parse(number_text);
         Ok(Self { number, assignment })
     }
 }
@@ -215,3 +312,47 @@ impl<'a> Deref for Groupwork<'a> {
         self.assignment
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_plain_number() {
+        assert_eq!(HwNumber::parse("7"), HwNumber::new("7"));
+        assert_eq!(HwNumber::parse("10"), HwNumber::new("10"));
+    }
+
+    #[test]
+    fn parses_spaced_variant() {
+        assert_eq!(
+            HwNumber::parse("7 (Makeup)"),
+            HwNumber::with_variant("7", "Makeup")
+        );
+    }
+
+    #[test]
+    fn parses_unspaced_variant() {
+        assert_eq!(
+            HwNumber::parse("7(Makeup)"),
+            HwNumber::with_variant("7", "Makeup")
+        );
+    }
+
+    #[test]
+    fn parses_dashed_variant() {
+        assert_eq!(
+            HwNumber::parse("7-Late"),
+            HwNumber::with_variant("7", "Late")
+        );
+        assert_eq!(
+            HwNumber::parse("7 - Late"),
+            HwNumber::with_variant("7", "Late")
+        );
+    }
+
+    #[test]
+    fn keeps_text_without_a_number() {
+        assert_eq!(HwNumber::parse("Makeup"), HwNumber::new("Makeup"));
+    }
+}
@@ -6,6 +6,7 @@ use std::{iter, option};
 
 use futures::future::{self, OptionFuture};
 use gradescope_api::regrade::Regrade;
+use gradescope_api::types::Points;
 use itertools::Itertools;
 
 use super::{Groupwork, HasHwNumber, HwNumber, Individual};
@@ -34,6 +35,14 @@ impl<Id, Gw> Pair<Id, Gw> {
         }
     }
 
+    pub fn id(&self) -> Option<&Id> {
+        self.id.as_ref()
+    }
+
+    pub fn gw(&self) -> Option<&Gw> {
+        self.gw.as_ref()
+    }
+
     #[allow(clippy::len_without_is_empty)]
     pub fn len(&self) -> usize {
         match (&self.id, &self.gw) {
@@ -225,5 +234,40 @@ impl<Id, Gw> Pair<Vec<Id>, Vec<Gw>> {
 
 pub type HwPair<'a> = Pair<Individual<'a>, Groupwork<'a>>;
 
+impl<'a> HwPair<'a> {
+    pub fn points_summary(&self) -> PointsSummary {
+        PointsSummary {
+            individual: self.id.map(|id| id.points()),
+            groupwork: self.gw.map(|gw| gw.points()),
+        }
+    }
+}
+
+/// Points available for each half of a homework pair
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PointsSummary {
+    individual: Option<Points>,
+    groupwork: Option<Points>,
+}
+
+impl PointsSummary {
+    pub fn individual(self) -> Option<Points> {
+        self.individual
+    }
+
+    pub fn groupwork(self) -> Option<Points> {
+        self.groupwork
+    }
+
+    /// Combined points of the individual and groupwork, counting a missing half as zero
+    pub fn total(self) -> f32 {
+        [self.individual, self.groupwork]
+            .into_iter()
+            .flatten()
+            .map(Points::as_f32)
+            .sum()
+    }
+}
+
 pub type RegradesPair = SamePair<Vec<Regrade>>;
 pub type RegradeRefsPair<'a> = SamePair<Vec<&'a Regrade>>;
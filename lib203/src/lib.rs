@@ -1,2 +1,4 @@
+pub mod digest;
 pub mod exam;
 pub mod homework;
+pub mod topic;
@@ -0,0 +1,102 @@
+//! Tagging questions with course topics, so statistics can be aggregated by topic across the term.
+//!
+//! Tags are read from a plain text file with one question title per line, followed by a colon and a
+//! comma-separated list of topics. Blank lines and lines starting with `#` are ignored:
+//! ```text
+//! # HW 3
+//! Strong Induction: induction, proofs
+//! Euler Paths: graphs
+//! ```
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::{fmt, fs};
+
+use anyhow::{Context, Result};
+use gradescope_api::regrade::Regrade;
+use gradescope_api::types::QuestionTitle;
+use itertools::Itertools;
+use serde::Serialize;
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(transparent)]
+pub struct Topic {
+    name: String,
+}
+
+impl Topic {
+    pub fn new(name: String) -> Self {
+        Self { name }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.name
+    }
+}
+
+impl fmt::Display for Topic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.name.fmt(f)
+    }
+}
+
+/// Maps question titles to the topics they cover
+#[derive(Debug, Clone, Default)]
+pub struct TopicTags {
+    tags: HashMap<String, Vec<Topic>>,
+}
+
+impl TopicTags {
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("couldn't read tags file \"{}\"", path.display()))?;
+        Self::parse(&text)
+    }
+
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut tags = HashMap::<_, Vec<_>>::new();
+
+        for line in text.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            // Split on the last colon, since question titles may contain colons themselves
+            let (title, topics) = line
+                .rsplit_once(':')
+                .with_context(|| format!("couldn't split tag line \"{line}\""))?;
+            let topics = topics
+                .split(',')
+                .map(str::trim)
+                .filter(|topic| !topic.is_empty())
+                .map(|topic| Topic::new(topic.to_owned()));
+
+            tags.entry(title.trim().to_owned())
+                .or_default()
+                .extend(topics);
+        }
+
+        Ok(Self { tags })
+    }
+
+    /// The topics for a question, which are empty if the question is untagged
+    pub fn topics(&self, title: &QuestionTitle) -> &[Topic] {
+        self.tags
+            .get(title.as_str().trim())
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+}
+
+/// Counts regrades for each topic. A regrade on a question with several topics counts towards each
+/// of them, and regrades on untagged questions are not counted.
+pub fn count_regrades_by_topic<'a>(
+    tags: &'a TopicTags,
+    regrades: impl IntoIterator<Item = &'a Regrade>,
+) -> HashMap<&'a Topic, usize> {
+    regrades
+        .into_iter()
+        .flat_map(|regrade| tags.topics(regrade.question_title()))
+        .counts()
+}